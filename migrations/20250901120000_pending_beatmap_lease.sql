@@ -0,0 +1,13 @@
+-- Migration: Lease-based claiming for pending_beatmap
+-- Created: 2025-09-01
+-- Author: Osef
+-- Description: Workers lease rows instead of deleting them on claim, so a crash mid-processing
+--              only delays the beatmap until the lease expires
+-- Version: 1.0.0
+
+alter table pending_beatmap add column claimed_at timestamp null;
+alter table pending_beatmap add column lease_expires_at timestamp null;
+alter table pending_beatmap add column attempts integer not null default 0;
+
+-- Indexes --
+create index if not exists idx_pending_beatmap_lease_expires_at on pending_beatmap(lease_expires_at);
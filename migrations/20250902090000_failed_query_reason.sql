@@ -0,0 +1,29 @@
+-- Migration: Structured failure reasons for failed_query
+-- Created: 2025-09-02
+-- Author: Osef
+-- Description: Store why a checksum failed, how many times, and when it may be retried.
+--              Transient failures are retried with exponential backoff, the others are permanent.
+-- Version: 1.0.0
+
+-- Un seul enregistrement par hash (on garde le plus récent)
+delete from failed_query a using failed_query b where a.hash = b.hash and a.id < b.id;
+drop index if exists idx_failed_query_hash;
+create unique index idx_failed_query_hash on failed_query(hash);
+
+alter table failed_query add column reason varchar(32) not null default 'network';
+alter table failed_query add column message text null;
+alter table failed_query add column attempts integer not null default 1;
+alter table failed_query add column permanent boolean not null default false;
+alter table failed_query add column next_retry_at timestamp null;
+alter table failed_query add column updated_at timestamp default now();
+
+-- Les anciens échecs n'ont pas de cause connue : on les retente une fois pour les catégoriser
+update failed_query set next_retry_at = now();
+
+alter table failed_query alter column reason drop default;
+alter table failed_query add constraint valid_reason
+    check (reason in ('not_found', 'not_allowed_mode', 'parse_error', 'network', 'calc_error'));
+
+-- Indexes --
+create index if not exists idx_failed_query_reason on failed_query(reason);
+create index if not exists idx_failed_query_next_retry_at on failed_query(next_retry_at) where not permanent;
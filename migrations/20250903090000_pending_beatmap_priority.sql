@@ -0,0 +1,10 @@
+-- Migration: Priority lanes for pending_beatmap
+-- Created: 2025-09-03
+-- Author: Osef
+-- Description: Interactive requests (a user waiting on the site) are claimed before bulk imports
+-- Version: 1.0.0
+
+alter table pending_beatmap add column priority smallint not null default 0;
+
+-- Indexes --
+create index if not exists idx_pending_beatmap_priority_order on pending_beatmap(priority desc, created_at asc, id asc);
@@ -0,0 +1,24 @@
+-- Migration: Batch tracking for pending_beatmap
+-- Created: 2025-09-04
+-- Author: Osef
+-- Description: Remember which checksums were submitted together so clients can follow a batch
+-- Version: 1.0.0
+
+create table if not exists pending_batch (
+    id uuid primary key,
+    created_at timestamp default now()
+);
+
+create table if not exists pending_batch_item (
+    batch_id uuid not null references pending_batch(id) on delete cascade,
+    hash text not null,
+    item_order integer not null,
+    submit_status varchar(32) not null,
+    primary key (batch_id, hash),
+    constraint valid_submit_status
+        check (submit_status in ('queued', 'already_processed', 'previously_failed', 'duplicate'))
+);
+
+-- Indexes --
+create index if not exists idx_pending_batch_created_at on pending_batch(created_at);
+create index if not exists idx_pending_batch_item_hash on pending_batch_item(hash);
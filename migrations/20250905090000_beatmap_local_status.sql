@@ -0,0 +1,10 @@
+-- Migration: Local beatmap status
+-- Created: 2025-09-05
+-- Author: Osef
+-- Description: Allow beatmaps imported from .osz archives that the osu! API doesn't know,
+--              plus the approved/wip statuses returned by rank_status_to_string
+-- Version: 1.0.0
+
+alter table beatmap drop constraint if exists valid_status;
+alter table beatmap add constraint valid_status
+    check (status in ('pending', 'ranked', 'approved', 'qualified', 'loved', 'graveyard', 'wip', 'local'));
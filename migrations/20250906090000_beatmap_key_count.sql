@@ -0,0 +1,26 @@
+-- Migration: Key count and note density on beatmap
+-- Created: 2025-09-06
+-- Author: Osef
+-- Description: Store the mania key count (4K to 10K) and note density of each beatmap.
+--              Only 4K maps get MSD; other keymodes are stored with metadata and density only
+-- Version: 1.0.0
+
+alter table beatmap add column key_count integer null;
+alter table beatmap add column avg_nps decimal(8,2) null;
+alter table beatmap add column peak_nps decimal(8,2) null;
+
+-- En mania, CircleSize est le nombre de colonnes
+update beatmap set key_count = round(cs)::integer where mode = 3;
+
+alter table beatmap add constraint valid_key_count check (key_count is null or key_count between 1 and 18);
+
+-- Les maps refusées parce qu'elles n'étaient pas en 4K repassent par la file ;
+-- celles qui ne sont pas du mania seront de nouveau refusées
+insert into pending_beatmap (hash)
+select hash from failed_query where reason = 'not_allowed_mode'
+on conflict (hash) do nothing;
+
+delete from failed_query where reason = 'not_allowed_mode';
+
+-- Indexes --
+create index if not exists idx_beatmap_key_count on beatmap(key_count);
@@ -0,0 +1,24 @@
+-- Migration: Long note statistics on beatmap
+-- Created: 2025-09-07
+-- Author: Osef
+-- Description: Store LN count, LN ratio and average LN length of each beatmap
+-- Version: 1.0.0
+
+alter table beatmap add column ln_count integer null;
+alter table beatmap add column ln_ratio decimal(5,4) null;
+alter table beatmap add column avg_ln_length integer null;
+
+-- En mania, l'API compte les LN dans count_sliders ; la durée moyenne demande le .osu
+update beatmap
+set ln_count = count_sliders,
+    ln_ratio = case
+        when count_circles + count_sliders > 0
+        then round(count_sliders::numeric / (count_circles + count_sliders), 4)
+        else 0
+    end
+where mode = 3;
+
+alter table beatmap add constraint valid_ln_ratio check (ln_ratio is null or ln_ratio between 0 and 1);
+
+-- Indexes --
+create index if not exists idx_beatmap_ln_ratio on beatmap(ln_ratio);
@@ -0,0 +1,15 @@
+-- Migration: Beatmap density timeline
+-- Created: 2025-09-08
+-- Author: Osef
+-- Description: Per-window NPS, chord density and per-column load of each beatmap,
+--              stored as one jsonb document per beatmap for the difficulty graph
+-- Version: 1.0.0
+
+create table if not exists beatmap_density (
+    beatmap_id integer primary key references beatmap(id) on delete cascade,
+    window_ms integer not null,
+    timeline jsonb not null,
+    created_at timestamp default now(),
+    updated_at timestamp default now(),
+    constraint valid_window_ms check (window_ms > 0)
+);
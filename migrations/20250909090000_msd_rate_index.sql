@@ -0,0 +1,9 @@
+-- Migration: Index MSD by beatmap and rate
+-- Created: 2025-09-09
+-- Author: Osef
+-- Description: Searches and lookups are no longer limited to rate = 1.0
+-- Version: 1.0.0
+
+-- Indexes --
+create index if not exists idx_msd_beatmap_id_rate on msd(beatmap_id, rate);
+create index if not exists idx_msd_rate_overall on msd(rate, overall);
@@ -0,0 +1,11 @@
+-- Migration: MSD calculator version
+-- Created: 2025-09-10
+-- Author: Osef
+-- Description: Record which MinaCalc version produced each msd row, so rows left
+--              by an older calculator can be recalculated. Existing rows are unknown (null)
+-- Version: 1.0.0
+
+alter table msd add column calc_version integer null;
+
+-- Indexes --
+create index if not exists idx_msd_calc_version on msd(calc_version);
@@ -0,0 +1,45 @@
+-- Migration: Typed MSD pattern columns
+-- Created: 2025-09-11
+-- Author: Osef
+-- Description: Store the two strongest skillsets of each msd row as indexable columns,
+--              with the primary/secondary ratio, instead of parsing the main_pattern JSON string
+-- Version: 1.0.0
+
+alter table msd add column primary_pattern varchar(16) null;
+alter table msd add column secondary_pattern varchar(16) null;
+alter table msd add column pattern_dominance decimal(6,3) null;
+
+-- Même classement que calculate_main_pattern : valeur décroissante, ordre des skillsets en cas d'égalité
+update msd m
+set primary_pattern = r.names[1],
+    secondary_pattern = r.names[2],
+    -- Plafonnée comme dans calculate_pattern_profile : un second skillset presque nul déborderait decimal(6,3)
+    pattern_dominance = case when r.vals[2] > 0 then least(round(r.vals[1] / r.vals[2], 3), 999) end
+from (
+    select s.id,
+           array_agg(p.name order by p.value desc, p.ord) as names,
+           array_agg(p.value order by p.value desc, p.ord) as vals
+    from msd s
+    cross join lateral (values
+        (1, 'stream', s.stream),
+        (2, 'jumpstream', s.jumpstream),
+        (3, 'handstream', s.handstream),
+        (4, 'stamina', s.stamina),
+        (5, 'jackspeed', s.jackspeed),
+        (6, 'chordjack', s.chordjack),
+        (7, 'technical', s.technical)
+    ) as p(ord, name, value)
+    where s.stream is not null
+    group by s.id
+) r
+where r.id = m.id;
+
+alter table msd add constraint valid_primary_pattern check (primary_pattern in
+    ('stream', 'jumpstream', 'handstream', 'stamina', 'jackspeed', 'chordjack', 'technical'));
+alter table msd add constraint valid_secondary_pattern check (secondary_pattern in
+    ('stream', 'jumpstream', 'handstream', 'stamina', 'jackspeed', 'chordjack', 'technical'));
+alter table msd add constraint valid_pattern_dominance check (pattern_dominance >= 1);
+
+-- Indexes --
+create index if not exists idx_msd_primary_pattern on msd(primary_pattern, secondary_pattern) where rate = 1.0;
+create index if not exists idx_msd_secondary_pattern on msd(secondary_pattern) where rate = 1.0;
@@ -0,0 +1,9 @@
+-- Migration: Indexes for listing sort keys
+-- Created: 2025-09-12
+-- Author: Osef
+-- Description: Index the beatmap columns the filtered listing can be sorted by
+-- Version: 1.0.0
+
+-- Indexes --
+create index if not exists idx_beatmap_bpm on beatmap(bpm);
+create index if not exists idx_beatmap_total_time on beatmap(total_time);
@@ -0,0 +1,33 @@
+-- Migration: Full-text search on beatmapsets
+-- Created: 2025-09-13
+-- Author: Osef
+-- Description: Weighted tsvector over artist/title (with unicode variants), creator, source and tags
+--              for ranked word search, and trigram indexes for field-qualified substring search.
+--              Tags are filled from the osu! API from now on; existing sets get them when re-fetched
+-- Version: 1.0.0
+
+create extension if not exists pg_trgm;
+
+-- array_to_string n'est que STABLE : une colonne générée exige une fonction IMMUTABLE
+create or replace function beatmapset_tags_text(tags text[]) returns text
+    language sql immutable parallel safe
+    as $$ select coalesce(array_to_string(tags, ' '), '') $$;
+
+alter table beatmapset add column search_vector tsvector generated always as (
+    setweight(to_tsvector('simple',
+        coalesce(artist, '') || ' ' || coalesce(artist_unicode, '') || ' ' ||
+        coalesce(title, '') || ' ' || coalesce(title_unicode, '')), 'A') ||
+    setweight(to_tsvector('simple', coalesce(creator, '')), 'B') ||
+    setweight(to_tsvector('simple', coalesce(source, '')), 'C') ||
+    setweight(to_tsvector('simple', beatmapset_tags_text(tags)), 'D')
+) stored;
+
+-- Indexes --
+create index if not exists idx_beatmapset_search_vector on beatmapset using gin(search_vector);
+create index if not exists idx_beatmapset_artist_trgm on beatmapset using gin(artist gin_trgm_ops);
+create index if not exists idx_beatmapset_artist_unicode_trgm on beatmapset using gin(artist_unicode gin_trgm_ops);
+create index if not exists idx_beatmapset_title_trgm on beatmapset using gin(title gin_trgm_ops);
+create index if not exists idx_beatmapset_title_unicode_trgm on beatmapset using gin(title_unicode gin_trgm_ops);
+create index if not exists idx_beatmapset_creator_trgm on beatmapset using gin(creator gin_trgm_ops);
+create index if not exists idx_beatmapset_source_trgm on beatmapset using gin(source gin_trgm_ops);
+create index if not exists idx_beatmapset_tags on beatmapset using gin(tags);
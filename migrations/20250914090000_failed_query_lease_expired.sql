@@ -0,0 +1,10 @@
+-- Migration: lease_expired failure reason
+-- Created: 2025-09-14
+-- Author: Osef
+-- Description: Checksums abandoned after too many expired leases get their own reason
+--              instead of calc_error, which stays for MinaCalc errors.
+-- Version: 1.0.0
+
+alter table failed_query drop constraint valid_reason;
+alter table failed_query add constraint valid_reason
+    check (reason in ('not_found', 'not_allowed_mode', 'parse_error', 'network', 'calc_error', 'lease_expired'));
@@ -0,0 +1,8 @@
+-- Migration: Lowercase beatmapset tags
+-- Created: 2025-09-15
+-- Author: Osef
+-- Description: Tags are stored in lowercase so that `tag:` search can match whole elements
+--              with `tags @> array[...]` through the GIN index instead of scanning every set.
+-- Version: 1.0.0
+
+update beatmapset set tags = lower(tags::text)::text[] where tags is not null and tags::text <> lower(tags::text);
@@ -0,0 +1,60 @@
+//! # CLI
+//!
+//! Sous-commandes d'administration lancées à la place du serveur :
+//!
+//! ```text
+//! osu-backend import-osz <fichier.osz>...
+//! ```
+
+use crate::services::osz_import::{MAX_OSZ_BYTES, import_osz};
+use tracing::error;
+
+/// Exécute la sous-commande passée en argument, `None` s'il n'y en a pas (démarrage du serveur)
+pub async fn run(args: &[String]) -> Option<i32> {
+    let (command, rest) = args.split_first()?;
+    Some(match command.as_str() {
+        "import-osz" => import_osz_files(rest).await,
+        _ => {
+            eprintln!("Unknown command: {}", command);
+            eprintln!("Usage: osu-backend import-osz <file.osz>...");
+            2
+        }
+    })
+}
+
+async fn import_osz_files(paths: &[String]) -> i32 {
+    if paths.is_empty() {
+        eprintln!("Usage: osu-backend import-osz <file.osz>...");
+        return 2;
+    }
+
+    let mut code = 0;
+    for path in paths {
+        let bytes = match tokio::fs::read(path).await {
+            Ok(bytes) if bytes.len() <= MAX_OSZ_BYTES => bytes,
+            Ok(_) => {
+                error!("{}: archive too large", path);
+                code = 1;
+                continue;
+            }
+            Err(e) => {
+                error!("{}: {}", path, e);
+                code = 1;
+                continue;
+            }
+        };
+
+        match import_osz(bytes).await {
+            Ok(report) => println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({ "file": path, "report": report }))
+                    .unwrap_or_default()
+            ),
+            Err(e) => {
+                error!("{}: {}", path, e);
+                code = 1;
+            }
+        }
+    }
+    code
+}
@@ -56,6 +56,32 @@ impl Default for OsuApiConfig {
     }
 }
 
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            idle_sleep_secs: 10,
+            lease_secs: 300,
+            max_attempts: 3,
+            batch_retention_days: 7,
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            osu_files_dir: "data/osu_files".to_string(),
+        }
+    }
+}
+
+impl Default for AdminConfig {
+    fn default() -> Self {
+        Self { token: None }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         use tracing::warn;
@@ -67,6 +93,9 @@ impl Default for Config {
             logging: LoggingConfig::default(),
             cors: CorsConfig::default(),
             osu_api: OsuApiConfig::default(),
+            queue: QueueConfig::default(),
+            admin: AdminConfig::default(),
+            storage: StorageConfig::default(),
         }
     }
 }
@@ -76,6 +76,49 @@ impl OsuApiConfig {
     }
 }
 
+impl QueueConfig {
+    pub fn load() -> Self {
+        QueueConfig {
+            workers: var("QUEUE_WORKERS")
+                .unwrap_or_else(|_| Self::default().workers.to_string())
+                .parse()
+                .unwrap_or(Self::default().workers),
+            idle_sleep_secs: var("QUEUE_IDLE_SLEEP_SECS")
+                .unwrap_or_else(|_| Self::default().idle_sleep_secs.to_string())
+                .parse()
+                .unwrap_or(Self::default().idle_sleep_secs),
+            lease_secs: var("QUEUE_LEASE_SECS")
+                .unwrap_or_else(|_| Self::default().lease_secs.to_string())
+                .parse()
+                .unwrap_or(Self::default().lease_secs),
+            max_attempts: var("QUEUE_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| Self::default().max_attempts.to_string())
+                .parse()
+                .unwrap_or(Self::default().max_attempts),
+            batch_retention_days: var("QUEUE_BATCH_RETENTION_DAYS")
+                .unwrap_or_else(|_| Self::default().batch_retention_days.to_string())
+                .parse()
+                .unwrap_or(Self::default().batch_retention_days),
+        }
+    }
+}
+
+impl StorageConfig {
+    pub fn load() -> Self {
+        StorageConfig {
+            osu_files_dir: var("OSU_FILES_DIR").unwrap_or_else(|_| Self::default().osu_files_dir),
+        }
+    }
+}
+
+impl AdminConfig {
+    pub fn load() -> Self {
+        AdminConfig {
+            token: var("ADMIN_TOKEN").ok().filter(|t| !t.trim().is_empty()),
+        }
+    }
+}
+
 impl Config {
     /// Initialise le système de logging
     fn init_logging(level: &str, _format: &str) {
@@ -101,6 +144,9 @@ impl Config {
             logging: LoggingConfig::load(),
             cors: CorsConfig::load(),
             osu_api: OsuApiConfig::load(),
+            queue: QueueConfig::load(),
+            admin: AdminConfig::load(),
+            storage: StorageConfig::load(),
         };
 
         Self::init_logging(&config.logging.level, &config.logging.format);
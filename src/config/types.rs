@@ -30,6 +30,28 @@ pub struct OsuApiConfig {
     pub client_secret: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct QueueConfig {
+    pub workers: usize,
+    pub idle_sleep_secs: u64,
+    pub lease_secs: u64,
+    pub max_attempts: i32,
+    /// Les batches terminés sont supprimés après ce délai
+    pub batch_retention_days: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageConfig {
+    /// Dossier du cache local des fichiers .osu, indexé par md5
+    pub osu_files_dir: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AdminConfig {
+    /// Token Bearer attendu sur /api/admin, les routes admin sont désactivées si absent
+    pub token: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub server: ServerConfig,
@@ -38,4 +60,7 @@ pub struct Config {
     #[allow(dead_code)]
     pub cors: CorsConfig,
     pub osu_api: OsuApiConfig,
+    pub queue: QueueConfig,
+    pub admin: AdminConfig,
+    pub storage: StorageConfig,
 }
@@ -0,0 +1,30 @@
+use axum::{extract::State, Json, http::StatusCode, extract::Query};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::db::DatabaseManager;
+use crate::models::failed_query::FailedQuery;
+
+#[derive(Deserialize)]
+pub struct PurgeFailedQuery {
+    /// Supprime les échecs dont la dernière tentative date de plus de N jours
+    pub older_than_days: u32,
+}
+
+#[derive(Serialize)]
+pub struct PurgeFailedResponse {
+    pub deleted: u64,
+}
+
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Query(query): Query<PurgeFailedQuery>,
+) -> Result<Json<PurgeFailedResponse>, StatusCode> {
+    let pool = db.get_pool();
+
+    let cutoff = Utc::now().naive_utc() - Duration::days(query.older_than_days as i64);
+    let deleted = FailedQuery::delete_older_than(pool, cutoff)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(PurgeFailedResponse { deleted }))
+}
@@ -0,0 +1,65 @@
+use axum::{extract::State, Json, http::StatusCode, extract::Query};
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use crate::db::DatabaseManager;
+use crate::models::failed_query::{FailedQuery, FailureReason};
+
+#[derive(Deserialize)]
+pub struct FailedListQuery {
+    pub reason: Option<FailureReason>,
+    /// Date du dernier échec, bornes incluses
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub page: Option<usize>,
+    pub per_page: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct FailedListResponse {
+    pub failed: Vec<FailedQuery>,
+    pub total: usize,
+    pub page: usize,
+    pub per_page: usize,
+    pub total_pages: usize,
+}
+
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Query(query): Query<FailedListQuery>,
+) -> Result<Json<FailedListResponse>, StatusCode> {
+    let pool = db.get_pool();
+
+    let per_page = query.per_page.unwrap_or(50).clamp(1, 500);
+    let page = query.page.unwrap_or(1).max(1);
+
+    let from = query.from.and_then(|d| d.and_hms_opt(0, 0, 0));
+    let to = query
+        .to
+        .and_then(|d| d.checked_add_days(Days::new(1)))
+        .and_then(|d| d.and_hms_opt(0, 0, 0));
+
+    let total = FailedQuery::count(pool, query.reason, from, to)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let failed = FailedQuery::list(
+        pool,
+        query.reason,
+        from,
+        to,
+        per_page as i64,
+        ((page - 1) * per_page) as i64,
+    )
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let total_pages = (total + per_page as i64 - 1) / per_page as i64;
+
+    Ok(Json(FailedListResponse {
+        failed,
+        total: total as usize,
+        page,
+        per_page,
+        total_pages: total_pages as usize,
+    }))
+}
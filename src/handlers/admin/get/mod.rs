@@ -0,0 +1,2 @@
+pub mod failed;
+pub mod recalc;
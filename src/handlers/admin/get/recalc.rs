@@ -0,0 +1,31 @@
+use axum::{extract::State, Json, http::StatusCode};
+use serde::Serialize;
+use crate::db::DatabaseManager;
+use crate::models::extended::beatmap::BeatmapExtended;
+use crate::services::msd_calculator::calc_version;
+use crate::services::recalculation::{self, RecalcProgress};
+
+#[derive(Serialize)]
+pub struct RecalcStatusResponse {
+    pub current_calc_version: i32,
+    /// Beatmaps dont les MSD viennent encore d'une autre version
+    pub outdated: i64,
+    pub job: RecalcProgress,
+}
+
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+) -> Result<Json<RecalcStatusResponse>, StatusCode> {
+    let pool = db.get_pool();
+    let current_calc_version = calc_version();
+
+    let outdated = BeatmapExtended::count_outdated_msd(pool, current_calc_version)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(RecalcStatusResponse {
+        current_calc_version,
+        outdated,
+        job: recalculation::progress(),
+    }))
+}
@@ -0,0 +1,23 @@
+use axum::{Json, extract::Request, http::StatusCode};
+use tokio::sync::Semaphore;
+use tracing::error;
+use crate::helpers::upload::read_upload;
+use crate::services::osz_import::{MAX_OSZ_BYTES, OszImportReport, import_osz};
+
+/// Un import à la fois : chaque difficulté attend CALC_LOCK avec les workers de la file
+static IMPORT_SLOT: Semaphore = Semaphore::const_new(1);
+
+/// Importe une archive .osz envoyée brute (body) ou en multipart (champ `file`)
+pub async fn handler(request: Request) -> Result<Json<OszImportReport>, (StatusCode, String)> {
+    let _slot = IMPORT_SLOT.try_acquire().map_err(|_| {
+        (StatusCode::TOO_MANY_REQUESTS, "An import is already in progress, retry later".to_string())
+    })?;
+    let bytes = read_upload(request, "file", MAX_OSZ_BYTES).await?;
+
+    let report = import_osz(bytes.to_vec()).await.map_err(|e| {
+        error!("Failed to import osz: {}", e);
+        (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+    })?;
+
+    Ok(Json(report))
+}
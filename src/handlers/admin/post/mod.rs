@@ -0,0 +1,3 @@
+pub mod import_osz;
+pub mod recalc;
+pub mod retry_failed;
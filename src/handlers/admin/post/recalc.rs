@@ -0,0 +1,19 @@
+use axum::{extract::State, Json, http::StatusCode};
+use crate::db::DatabaseManager;
+use crate::services::recalculation::{self, RecalcProgress};
+
+/// Lance le recalcul des MSD d'une ancienne version du calculateur
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+) -> Result<Json<RecalcProgress>, StatusCode> {
+    let pool = db.get_pool().clone();
+
+    let started = recalculation::start(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !started {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    Ok(Json(recalculation::progress()))
+}
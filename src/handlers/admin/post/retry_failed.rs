@@ -0,0 +1,26 @@
+use axum::{extract::State, Json, http::StatusCode, extract::Path};
+use serde::Serialize;
+use crate::db::DatabaseManager;
+use crate::models::failed_query::FailedQuery;
+
+#[derive(Serialize)]
+pub struct RetryFailedResponse {
+    pub hash: String,
+    pub queued: bool,
+}
+
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(hash): Path<String>,
+) -> Result<Json<RetryFailedResponse>, StatusCode> {
+    let pool = db.get_pool();
+
+    let moved = FailedQuery::retry_by_hash(pool, &hash)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !moved {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(RetryFailedResponse { hash, queued: true }))
+}
@@ -0,0 +1,103 @@
+use axum::{Json, extract::Request, http::StatusCode};
+use serde::Serialize;
+use crate::helpers::osu_file::{MAX_OSU_FILE_BYTES, OsuFileError, validate_osu_file};
+use crate::helpers::upload::read_upload;
+use crate::models::extended::msd::MSDExtended;
+use crate::services::beatmap_queue::processor::BeatmapProcessor;
+use crate::services::msd_calculator::osu_to_notes;
+use crate::services::osu_file_store::OsuFileStore;
+use tokio::sync::Semaphore;
+
+/// Analyses simultanées : chacune attend CALC_LOCK avec les workers de la file
+const MAX_CONCURRENT_ANALYSES: usize = 2;
+
+static ANALYSIS_SLOTS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_ANALYSES);
+
+#[derive(Serialize)]
+pub struct AnalyzeResponse {
+    pub md5: String,
+    pub hit_objects: usize,
+    pub key_count: u32,
+    pub avg_nps: f32,
+    pub peak_nps: f32,
+    pub ln_count: u32,
+    pub ln_ratio: f32,
+    pub avg_ln_length: f32,
+    /// Vide hors 4K : MinaCalc ne note que le 4K
+    pub msd: Vec<MSDExtended>,
+}
+
+#[derive(Serialize)]
+pub struct AnalyzeErrorResponse {
+    pub error: String,
+    pub line: Option<usize>,
+    pub hit_object: Option<usize>,
+}
+
+type AnalyzeError = (StatusCode, Json<AnalyzeErrorResponse>);
+
+fn error(status: StatusCode, message: impl Into<String>) -> AnalyzeError {
+    (
+        status,
+        Json(AnalyzeErrorResponse {
+            error: message.into(),
+            line: None,
+            hit_object: None,
+        }),
+    )
+}
+
+impl From<OsuFileError> for AnalyzeErrorResponse {
+    fn from(e: OsuFileError) -> Self {
+        Self {
+            error: e.to_string(),
+            line: e.line,
+            hit_object: e.hit_object,
+        }
+    }
+}
+
+/// Calcule les MSD d'un .osu envoyé brut (body) ou en multipart (champ `file`), sans rien enregistrer
+pub async fn handler(request: Request) -> Result<Json<AnalyzeResponse>, AnalyzeError> {
+    let _slot = ANALYSIS_SLOTS
+        .try_acquire()
+        .map_err(|_| error(StatusCode::TOO_MANY_REQUESTS, "Too many analyses in progress, retry later"))?;
+    let content = read_osu_file(request).await?;
+
+    let summary = validate_osu_file(&content)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(AnalyzeErrorResponse::from(e))))?;
+    let chart = osu_to_notes(&content).map_err(|e| error(StatusCode::BAD_REQUEST, e))?;
+    let density = chart.density();
+    let ln = chart.ln_stats();
+    let key_count = chart.key_count;
+
+    let msd = if chart.has_msd() {
+        // MinaCalc est synchrone et coûteux : hors des threads du runtime
+        tokio::task::spawn_blocking(move || BeatmapProcessor::instance().calculate_msd(chart.notes))
+            .await
+            .map_err(|_| error(StatusCode::INTERNAL_SERVER_ERROR, "MSD calculation crashed"))?
+            .map_err(|e| error(StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))?
+    } else {
+        Vec::new()
+    };
+
+    Ok(Json(AnalyzeResponse {
+        md5: OsuFileStore::md5_hex(content.as_bytes()),
+        hit_objects: summary.hit_object_count,
+        key_count,
+        avg_nps: density.avg_nps,
+        peak_nps: density.peak_nps,
+        ln_count: ln.ln_count,
+        ln_ratio: ln.ln_ratio,
+        avg_ln_length: ln.avg_ln_length,
+        msd,
+    }))
+}
+
+async fn read_osu_file(request: Request) -> Result<String, AnalyzeError> {
+    let bytes = read_upload(request, "file", MAX_OSU_FILE_BYTES)
+        .await
+        .map_err(|(status, message)| error(status, message))?;
+
+    String::from_utf8(bytes.to_vec()).map_err(|_| error(StatusCode::BAD_REQUEST, "File is not valid UTF-8"))
+}
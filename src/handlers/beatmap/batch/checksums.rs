@@ -1,17 +1,30 @@
 use crate::db::DatabaseManager;
+use crate::models::extended::beatmap::BeatmapExtended;
+use crate::models::failed_query::FailedQuery;
+use crate::models::pending_batch::{PendingBatch, SubmitStatus};
 use crate::models::pending_beatmap::PendingBeatmap;
 use axum::{Json, extract::State, http::StatusCode};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
 
 #[derive(Deserialize)]
 pub struct BatchChecksumsRequest {
     pub checksums: Vec<String>,
 }
 
+#[derive(Serialize)]
+pub struct BatchChecksumStatus {
+    pub checksum: String,
+    pub status: SubmitStatus,
+}
+
 #[derive(Serialize)]
 pub struct BatchChecksumsResponse {
     pub message: String,
     pub status: String,
+    pub batch_id: Option<Uuid>,
+    pub checksums: Vec<BatchChecksumStatus>,
 }
 
 pub async fn handler(
@@ -24,15 +37,64 @@ pub async fn handler(
         return Ok(Json(BatchChecksumsResponse {
             message: "No checksum provided".to_string(),
             status: "400".to_string(),
+            batch_id: None,
+            checksums: Vec::new(),
         }));
     }
 
-    let inserted = PendingBeatmap::bulk_insert(db.get_pool(), &batch)
+    let pool = db.get_pool();
+
+    let processed: HashSet<String> = BeatmapExtended::existing_checksums(pool, &batch)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .collect();
+    let failed: HashSet<String> = FailedQuery::blocked_hashes(pool, &batch)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .collect();
+
+    // Classer chaque checksum dans l'ordre de soumission
+    let mut seen = HashSet::new();
+    let checksums: Vec<BatchChecksumStatus> = batch
+        .into_iter()
+        .map(|checksum| {
+            let status = if !seen.insert(checksum.clone()) {
+                SubmitStatus::Duplicate
+            } else if processed.contains(&checksum) {
+                SubmitStatus::AlreadyProcessed
+            } else if failed.contains(&checksum) {
+                SubmitStatus::PreviouslyFailed
+            } else {
+                SubmitStatus::Queued
+            };
+            BatchChecksumStatus { checksum, status }
+        })
+        .collect();
+
+    let to_queue: Vec<String> = checksums
+        .iter()
+        .filter(|c| c.status == SubmitStatus::Queued)
+        .map(|c| c.checksum.clone())
+        .collect();
+    let inserted = PendingBeatmap::bulk_insert(pool, &to_queue)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let items: Vec<(String, SubmitStatus)> = checksums
+        .iter()
+        .filter(|c| c.status != SubmitStatus::Duplicate)
+        .map(|c| (c.checksum.clone(), c.status))
+        .collect();
+    let batch_id = PendingBatch::insert(pool, &items)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     Ok(Json(BatchChecksumsResponse {
         message: format!("{} checksums added to processing queue", inserted),
         status: "200".to_string(),
+        batch_id: Some(batch_id),
+        checksums,
     }))
 }
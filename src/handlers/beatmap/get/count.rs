@@ -8,7 +8,10 @@ use crate::models::extended::beatmap::query::get_all_stats;
 pub struct BeatmapCountResponse {
     pub total_beatmaps: i64,
     pub total_beatmapsets: i64,
+    /// Beatmaps par skillset dominant
     pub patterns: HashMap<String, u64>,
+    /// Beatmaps par deuxième skillset
+    pub secondary_patterns: HashMap<String, u64>,
 }
 
 pub async fn handler(
@@ -31,6 +34,7 @@ pub async fn handler(
     Ok(Json(BeatmapCountResponse {
         total_beatmaps,
         total_beatmapsets,
-        patterns,
+        patterns: patterns.primary,
+        secondary_patterns: patterns.secondary,
     }))
 }
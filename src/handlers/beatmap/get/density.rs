@@ -0,0 +1,48 @@
+use axum::{extract::State, Json, http::StatusCode, extract::Path};
+use tracing::error;
+use crate::db::DatabaseManager;
+use crate::models::beatmap_density::{BeatmapDensity, DENSITY_WINDOW_MS};
+use crate::models::extended::beatmap::BeatmapExtended;
+use crate::services::msd_calculator::osu_to_notes;
+use crate::services::osu_file_store::OsuFileStore;
+
+/// Timeline de densité d'une beatmap, par id osu!
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(osu_id): Path<i32>,
+) -> Result<Json<BeatmapDensity>, StatusCode> {
+    let pool = db.get_pool();
+
+    if let Some(density) = BeatmapDensity::find_by_osu_id(pool, osu_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        return Ok(Json(density));
+    }
+
+    // Beatmap traitée avant l'ajout des timelines : calculée depuis le .osu puis enregistrée
+    let beatmap = BeatmapExtended::find_by_osu_id(pool, osu_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let osu_file = OsuFileStore::instance()
+        .fetch(&beatmap.file_md5, &beatmap.file_path)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch osu file for beatmap {}: {}", osu_id, e);
+            StatusCode::BAD_GATEWAY
+        })?;
+    let chart = osu_to_notes(&osu_file).map_err(|_| StatusCode::UNPROCESSABLE_ENTITY)?;
+    let timeline = chart.timeline(DENSITY_WINDOW_MS);
+
+    BeatmapDensity::upsert(pool, beatmap.id, &timeline)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(BeatmapDensity {
+        beatmap_id: beatmap.id,
+        osu_id: beatmap.osu_id,
+        timeline,
+    }))
+}
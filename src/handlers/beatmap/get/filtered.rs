@@ -2,6 +2,7 @@ use axum::{extract::State, Json, http::StatusCode, extract::Query};
 use serde::{Serialize};
 use crate::{db::DatabaseManager};
 use crate::models::short::complete::types::BeatmapsetCompleteShort;
+use crate::helpers::query_language::{QueryError, apply_query};
 use crate::models::Filters;
 
 #[derive(Serialize)]
@@ -11,13 +12,62 @@ pub struct BeatmapFiltersResponse {
     pub page: usize,
     pub per_page: usize,
     pub total_pages: usize,
+    /// À repasser en `cursor` pour la page suivante, absent sur la dernière page
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct FiltersErrorResponse {
+    pub error: String,
+    /// Position et critère fautifs dans `q`
+    pub position: Option<usize>,
+    pub token: Option<String>,
+}
+
+pub type FiltersError = (StatusCode, Json<FiltersErrorResponse>);
+
+pub fn error(status: StatusCode, message: impl Into<String>) -> FiltersError {
+    (
+        status,
+        Json(FiltersErrorResponse {
+            error: message.into(),
+            position: None,
+            token: None,
+        }),
+    )
+}
+
+impl From<QueryError> for FiltersErrorResponse {
+    fn from(e: QueryError) -> Self {
+        Self {
+            error: e.message,
+            position: Some(e.position),
+            token: Some(e.token),
+        }
+    }
+}
+
+/// Applique la requête avancée `q` aux autres paramètres
+pub fn resolve_filters(mut query: Filters) -> Result<Filters, FiltersError> {
+    if let Some(q) = query.q.take() {
+        apply_query(&mut query, &q).map_err(|e| (StatusCode::BAD_REQUEST, Json(FiltersErrorResponse::from(e))))?;
+    }
+    Ok(query)
 }
 
 pub async fn handler(
     State(db): State<DatabaseManager>,
     Query(query): Query<Filters>,
-) -> Result<Json<BeatmapFiltersResponse>, StatusCode> {
+) -> Result<Json<BeatmapFiltersResponse>, FiltersError> {
     let pool = db.get_pool();
+    let query = resolve_filters(query)?;
+
+    // Un curseur produit avec un autre tri ne désigne pas une position dans celui-ci
+    if let Some(cursor) = &query.cursor {
+        if !cursor.matches(&query) {
+            return Err(error(StatusCode::BAD_REQUEST, "Cursor does not match the requested sort"));
+        }
+    }
 
     // Pagination - utiliser les paramètres des filtres
     let per_page = query.per_page.unwrap_or(10);
@@ -25,20 +75,21 @@ pub async fn handler(
 
     let total = BeatmapsetCompleteShort::count_by_filters(pool, &query)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|_| error(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
-    let beatmaps = BeatmapsetCompleteShort::find_by_filters(pool, &query)
+    let result = BeatmapsetCompleteShort::find_by_filters(pool, &query)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|_| error(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
     // Calculer le nombre total de pages
     let total_pages = (total + per_page as i64 - 1) / per_page as i64;
 
     Ok(Json(BeatmapFiltersResponse {
-        beatmaps,
+        beatmaps: result.beatmaps,
         total: total as usize,
         page,
         per_page,
         total_pages: total_pages as usize,
+        next_cursor: result.next_cursor,
     }))
 }
@@ -1,4 +1,6 @@
 pub mod count;
+pub mod density;
 pub mod filtered;
 pub mod by_id_extended;
-pub mod random;
\ No newline at end of file
+pub mod msd;
+pub mod random;
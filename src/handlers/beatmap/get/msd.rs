@@ -0,0 +1,56 @@
+use axum::{extract::{Path, Query, State}, Json, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use crate::db::DatabaseManager;
+use crate::models::extended::beatmap::BeatmapExtended;
+use crate::models::extended::msd::MSDExtended;
+use crate::services::rate_msd::{MAX_RATE, MIN_RATE, MsdSource, msd_at_rate};
+
+#[derive(Deserialize)]
+pub struct RateQuery {
+    pub rate: Option<f32>,
+}
+
+#[derive(Serialize)]
+pub struct BeatmapRateMsdResponse {
+    pub osu_id: i32,
+    pub rate: f32,
+    pub source: MsdSource,
+    /// Vrai si les valeurs ne viennent pas d'un rate stocké tel quel
+    pub interpolated: bool,
+    pub msd: MSDExtended,
+}
+
+/// MSD d'une beatmap à un rate quelconque (`?rate=1.15`, 1.0 par défaut)
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(osu_id): Path<i32>,
+    Query(query): Query<RateQuery>,
+) -> Result<Json<BeatmapRateMsdResponse>, StatusCode> {
+    let pool = db.get_pool();
+    let rate = query.rate.unwrap_or(1.0);
+    if !(MIN_RATE..=MAX_RATE).contains(&rate) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let beatmap = BeatmapExtended::find_by_osu_id(pool, osu_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let result = msd_at_rate(pool, &beatmap, rate)
+        .await
+        .map_err(|e| {
+            error!("Failed to get MSD at rate {} for beatmap {}: {}", rate, osu_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(BeatmapRateMsdResponse {
+        osu_id,
+        rate: (rate * 100.0).round() / 100.0,
+        source: result.source,
+        interpolated: result.source != MsdSource::Exact,
+        msd: result.msd,
+    }))
+}
@@ -3,6 +3,7 @@ use serde::{Serialize};
 use crate::{db::DatabaseManager};
 use crate::models::short::complete::types::BeatmapsetCompleteShort;
 use crate::models::Filters;
+use super::filtered::{FiltersError, error, resolve_filters};
 
 #[derive(Serialize)]
 pub struct BeatmapRandomResponse {
@@ -13,12 +14,13 @@ pub struct BeatmapRandomResponse {
 pub async fn handler(
     State(db): State<DatabaseManager>,
     Query(query): Query<Filters>,
-) -> Result<Json<BeatmapRandomResponse>, StatusCode> {
+) -> Result<Json<BeatmapRandomResponse>, FiltersError> {
     let pool = db.get_pool();
+    let query = resolve_filters(query)?;
 
     let beatmaps = BeatmapsetCompleteShort::random_by_filters(pool, &query)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|_| error(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
     let len = beatmaps.len();
     Ok(Json(BeatmapRandomResponse {
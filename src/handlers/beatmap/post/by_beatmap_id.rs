@@ -1,5 +1,5 @@
 use crate::db::DatabaseManager;
-use crate::models::pending_beatmap::PendingBeatmap;
+use crate::models::pending_beatmap::{PendingBeatmap, PendingPriority};
 use crate::services::osu_api::OsuApiService;
 use axum::{Json, extract::State, http::StatusCode};
 use serde::{Deserialize, Serialize};
@@ -34,6 +34,7 @@ pub async fn handler(
         db.get_pool(),
         &beatmap.checksum.ok_or(StatusCode::BAD_REQUEST)?,
         Some(payload.id),
+        PendingPriority::Interactive,
     )
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
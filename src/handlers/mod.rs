@@ -7,6 +7,8 @@
 // pub mod user;
 // pub mod product;
 
+pub mod admin;
+pub mod analyze;
 pub mod beatmap;
 pub mod help;
 pub mod status;
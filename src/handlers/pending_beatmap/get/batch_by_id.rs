@@ -0,0 +1,69 @@
+use axum::{extract::State, Json, http::StatusCode, extract::Path};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use uuid::Uuid;
+use crate::models::failed_query::FailureReason;
+use crate::models::pending_batch::{BatchItemStatus, PendingBatch, SubmitStatus};
+use crate::{db::DatabaseManager};
+
+#[derive(Serialize)]
+pub struct BatchItemProgress {
+    pub checksum: String,
+    pub submitted: SubmitStatus,
+    pub status: BatchItemStatus,
+    pub position: Option<i64>,
+    pub failure_reason: Option<FailureReason>,
+}
+
+#[derive(Serialize)]
+pub struct BatchProgressResponse {
+    pub batch_id: Uuid,
+    pub created_at: Option<NaiveDateTime>,
+    pub total: usize,
+    pub queued: usize,
+    pub processing: usize,
+    pub processed: usize,
+    pub failed: usize,
+    pub done: bool,
+    pub items: Vec<BatchItemProgress>,
+}
+
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(batch_id): Path<Uuid>,
+) -> Result<Json<BatchProgressResponse>, StatusCode> {
+    let pool = db.get_pool();
+
+    let batch = PendingBatch::find_by_id(pool, batch_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let items: Vec<BatchItemProgress> = PendingBatch::items(pool, batch_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|item| BatchItemProgress {
+            status: item.status(),
+            checksum: item.hash,
+            submitted: item.submit_status,
+            position: item.position,
+            failure_reason: item.failure_reason,
+        })
+        .collect();
+
+    let count = |status: BatchItemStatus| items.iter().filter(|i| i.status == status).count();
+    let queued = count(BatchItemStatus::Queued);
+    let processing = count(BatchItemStatus::Processing);
+
+    Ok(Json(BatchProgressResponse {
+        batch_id: batch.id,
+        created_at: batch.created_at,
+        total: items.len(),
+        queued,
+        processing,
+        processed: count(BatchItemStatus::Processed),
+        failed: count(BatchItemStatus::Failed),
+        done: queued + processing == 0,
+        items,
+    }))
+}
@@ -0,0 +1,53 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::{Stream, StreamExt};
+use std::convert::Infallible;
+use crate::models::pending_beatmap::PendingBeatmap;
+use crate::services::beatmap_queue::tracker::{self, TrackState};
+use crate::{db::DatabaseManager};
+
+/// Suit un checksum en SSE : position dans la file, puis `processed` ou `failed`
+pub async fn by_hash(
+    State(db): State<DatabaseManager>,
+    Path(hash): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    stream_for_hash(db, hash).await
+}
+
+/// Comme `by_hash`, à partir de l'id osu! de la beatmap
+pub async fn by_osu_id(
+    State(db): State<DatabaseManager>,
+    Path(id): Path<i32>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    let hash = PendingBeatmap::hash_by_osu_id(db.get_pool(), id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    stream_for_hash(db, hash).await
+}
+
+async fn stream_for_hash(
+    db: DatabaseManager,
+    hash: String,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    let pool = db.get_pool().clone();
+
+    // Checksum inconnu : 404 plutôt qu'un flux qui ne dira jamais rien
+    tracker::current_state(&pool, &hash)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let stream = tracker::track(pool, hash).map(|state: TrackState| {
+        let event = Event::default()
+            .event(state.name())
+            .json_data(&state)
+            .unwrap_or_default();
+        Ok(event)
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
@@ -1 +1,4 @@
-pub mod status_by_osu_id;
\ No newline at end of file
+pub mod batch_by_id;
+pub mod events;
+pub mod status_by_hash;
+pub mod status_by_osu_id;
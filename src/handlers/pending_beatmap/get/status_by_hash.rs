@@ -0,0 +1,16 @@
+use axum::{extract::State, Json, http::StatusCode, extract::Path};
+use crate::handlers::pending_beatmap::get::status_by_osu_id::PendingBeatmapStatusResponse;
+use crate::models::pending_beatmap::PendingBeatmap;
+use crate::{db::DatabaseManager};
+
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(hash): Path<String>,
+) -> Result<Json<PendingBeatmapStatusResponse>, StatusCode> {
+    let pool = db.get_pool();
+
+    let position: i64 = PendingBeatmap::position_by_hash(pool, &hash).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?.ok_or(StatusCode::NOT_FOUND)?;
+    let total: i64 = PendingBeatmap::count(pool).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(PendingBeatmapStatusResponse { position, total }))
+}
@@ -1,3 +1,4 @@
+use crate::services::msd_calculator::{MAX_KEYS, MIN_KEYS};
 use rosu_v2::model::{GameMode, beatmap::RankStatus};
 
 pub fn rank_status_to_string(status: &RankStatus) -> String {
@@ -16,18 +17,20 @@ pub fn build_file_path(beatmap_id: u32) -> String {
     format!("https://osu.ppy.sh/osu/{}", beatmap_id)
 }
 
-pub async fn osu_file_from_url(path_url: &str) -> Result<String, Box<dyn std::error::Error>> {
-    let response = reqwest::get(path_url).await?;
-    let body = response.text().await?;
-    Ok(body)
+/// Octets bruts du .osu : le checksum osu! porte sur le fichier tel quel, BOM compris
+pub async fn osu_file_from_url(path_url: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let response = reqwest::get(path_url).await?.error_for_status()?;
+    let body = response.bytes().await?;
+    Ok(body.to_vec())
 }
 
+/// Mania de 4K à 10K ; seul le 4K reçoit des MSD, les autres gardent métadonnées et densité
 pub async fn is_allowed_beatmap(mode: GameMode, cs: f32) -> bool {
     if mode != GameMode::Mania {
         return false;
     }
 
-    if cs != 4.0 {
+    if cs.fract() != 0.0 || !(MIN_KEYS as f32..=MAX_KEYS as f32).contains(&cs) {
         return false;
     }
 
@@ -1,5 +1,5 @@
 use bigdecimal::BigDecimal;
-use bigdecimal::FromPrimitive;
+use bigdecimal::{FromPrimitive, ToPrimitive};
 
 pub fn from_f32(value: f32) -> BigDecimal {
     BigDecimal::from_f32(value).unwrap()
@@ -9,3 +9,7 @@ pub fn from_f32(value: f32) -> BigDecimal {
 pub fn from_f64(value: f64) -> BigDecimal {
     BigDecimal::from_f64(value).unwrap()
 }
+
+pub fn to_f32(value: &BigDecimal) -> f32 {
+    value.to_f32().unwrap_or(0.0)
+}
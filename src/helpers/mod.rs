@@ -2,4 +2,8 @@ pub mod beatmap;
 pub mod common;
 pub mod help;
 pub mod msd;
+pub mod osu_file;
+pub mod query_language;
+pub mod search;
+pub mod upload;
 pub mod status;
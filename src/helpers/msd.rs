@@ -1,25 +1,59 @@
+use crate::models::MsdPattern;
 use minacalc_rs::Ssr;
 
+/// Plafond de la dominance : `pattern_dominance` est un decimal(6,3)
+const MAX_PATTERN_DOMINANCE: f32 = 999.0;
+
+/// Les deux skillsets dominants d'une MSD
+#[derive(Debug, Clone, Copy)]
+pub struct PatternProfile {
+    pub primary: MsdPattern,
+    pub secondary: MsdPattern,
+    /// Valeur du premier skillset divisée par celle du second (plafonnée), `None` si le second est nul
+    pub dominance: Option<f32>,
+}
+
+fn skillset_value(ssr: &Ssr, pattern: MsdPattern) -> f32 {
+    match pattern {
+        MsdPattern::Stream => ssr.stream,
+        MsdPattern::Jumpstream => ssr.jumpstream,
+        MsdPattern::Handstream => ssr.handstream,
+        MsdPattern::Stamina => ssr.stamina,
+        MsdPattern::Jackspeed => ssr.jackspeed,
+        MsdPattern::Chordjack => ssr.chordjack,
+        MsdPattern::Technical => ssr.technical,
+    }
+}
+
+/// Skillsets par rating décroissant (tri stable : l'ordre de `MsdPattern::ALL` départage)
+fn ranked_patterns(ssr: &Ssr) -> Vec<(MsdPattern, f32)> {
+    let mut patterns: Vec<_> = MsdPattern::ALL
+        .iter()
+        .map(|&pattern| (pattern, skillset_value(ssr, pattern)))
+        .collect();
+    patterns.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    patterns
+}
+
+pub fn calculate_pattern_profile(ssr: &Ssr) -> PatternProfile {
+    let ranked = ranked_patterns(ssr);
+    let (primary, primary_value) = ranked[0];
+    let (secondary, secondary_value) = ranked[1];
+
+    PatternProfile {
+        primary,
+        secondary,
+        // Un second skillset presque nul ferait déborder la colonne
+        dominance: (secondary_value > 0.0).then(|| (primary_value / secondary_value).min(MAX_PATTERN_DOMINANCE)),
+    }
+}
+
+/// Les 2 premiers patterns en tableau JSON, conservé pour les clients existants
 pub fn calculate_main_pattern(ssr: &Ssr) -> String {
-    let patterns = vec![
-        ("stream", ssr.stream),
-        ("jumpstream", ssr.jumpstream),
-        ("handstream", ssr.handstream),
-        ("stamina", ssr.stamina),
-        ("jackspeed", ssr.jackspeed),
-        ("chordjack", ssr.chordjack),
-        ("technical", ssr.technical),
-    ];
-
-    // Trier par rating décroissant
-    let mut sorted_patterns: Vec<_> = patterns.into_iter().collect();
-    sorted_patterns.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Prendre les 2 premiers patterns
-    let top_2: Vec<_> = sorted_patterns
+    let top_2: Vec<_> = ranked_patterns(ssr)
         .into_iter()
         .take(2)
-        .map(|(pattern, _)| pattern)
+        .map(|(pattern, _)| pattern.as_column_name())
         .collect();
 
     serde_json::to_string(&top_2).unwrap()
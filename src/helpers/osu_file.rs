@@ -0,0 +1,268 @@
+use crate::services::msd_calculator::{MAX_KEYS, MIN_KEYS};
+use serde::Serialize;
+use std::fmt;
+
+/// Taille maximale acceptée pour un .osu (upload ou entrée d'archive)
+pub const MAX_OSU_FILE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Erreur de lecture d'un .osu, localisée sur la ligne (et le hit object) fautive
+#[derive(Debug, Clone, Serialize)]
+pub struct OsuFileError {
+    pub message: String,
+    pub line: Option<usize>,
+    pub hit_object: Option<usize>,
+}
+
+impl OsuFileError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            hit_object: None,
+        }
+    }
+
+    fn at(line: usize, hit_object: usize, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: Some(line),
+            hit_object: Some(hit_object),
+        }
+    }
+}
+
+impl fmt::Display for OsuFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.hit_object) {
+            (Some(line), Some(index)) => write!(f, "line {} (hit object #{}): {}", line, index, self.message),
+            (Some(line), None) => write!(f, "line {}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for OsuFileError {}
+
+/// Ce qu'on sait d'un .osu après validation
+#[derive(Debug, Clone)]
+pub struct OsuFileSummary {
+    pub hit_object_count: usize,
+}
+
+/// Lignes utiles d'un .osu avec leur numéro (à partir de 1) et leur section
+fn section_lines(content: &str) -> Vec<(usize, &str, &str)> {
+    let mut section = "";
+    let mut lines = Vec::new();
+
+    for (index, raw) in content.trim_start_matches('\u{feff}').lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            section = &line[1..line.len() - 1];
+            continue;
+        }
+        lines.push((index + 1, section, line));
+    }
+
+    lines
+}
+
+/// CircleSize de [Difficulty] : le nombre de colonnes en mania
+pub fn read_circle_size(content: &str) -> Option<f32> {
+    section_lines(content)
+        .into_iter()
+        .filter(|(_, section, _)| *section == "Difficulty")
+        .filter_map(|(_, _, line)| line.split_once(':'))
+        .find(|(key, _)| key.trim() == "CircleSize")
+        .and_then(|(_, value)| value.trim().parse().ok())
+}
+
+/// Valide un .osu avant conversion : en-tête, mode mania 4K à 10K et chaque ligne de [HitObjects].
+///
+/// `OsuParser` ignore les lignes qu'il ne comprend pas ; ici chaque erreur
+/// indique la ligne (numérotée à partir de 1) et l'index du hit object.
+pub fn validate_osu_file(content: &str) -> Result<OsuFileSummary, OsuFileError> {
+    let content = content.trim_start_matches('\u{feff}');
+    let first_line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    if !first_line.trim_start().starts_with("osu file format v") {
+        return Err(OsuFileError::new("Not an osu file (missing 'osu file format' header)"));
+    }
+
+    let mut mode = 0;
+    let mut circle_size = None;
+    let mut hit_objects = Vec::new();
+
+    for (line_number, section, line) in section_lines(content) {
+        match section {
+            "General" | "Difficulty" => {
+                let Some((key, value)) = line.split_once(':') else { continue };
+                match key.trim() {
+                    "Mode" => {
+                        mode = value.trim().parse().map_err(|_| OsuFileError {
+                            message: format!("Invalid Mode '{}'", value.trim()),
+                            line: Some(line_number),
+                            hit_object: None,
+                        })?
+                    }
+                    "CircleSize" => circle_size = value.trim().parse::<f32>().ok(),
+                    _ => {}
+                }
+            }
+            "HitObjects" => hit_objects.push((line_number, line)),
+            _ => {}
+        }
+    }
+
+    if mode != 3 {
+        return Err(OsuFileError::new(format!("Map is not mania (Mode: {})", mode)));
+    }
+    let circle_size = circle_size.ok_or_else(|| OsuFileError::new("Missing CircleSize in [Difficulty]"))?;
+    if circle_size.fract() != 0.0 || !(MIN_KEYS as f32..=MAX_KEYS as f32).contains(&circle_size) {
+        return Err(OsuFileError::new(format!(
+            "Only {}K to {}K maps are supported (CircleSize: {})",
+            MIN_KEYS, MAX_KEYS, circle_size
+        )));
+    }
+    if hit_objects.is_empty() {
+        return Err(OsuFileError::new("No hit objects in [HitObjects]"));
+    }
+
+    for (number, (line, hit_object)) in hit_objects.iter().enumerate() {
+        validate_hit_object(hit_object).map_err(|message| OsuFileError::at(*line, number + 1, message))?;
+    }
+
+    Ok(OsuFileSummary {
+        hit_object_count: hit_objects.len(),
+    })
+}
+
+/// `x,y,time,type,hitSound,...` ; les holds (bit 7) portent leur fin dans `endTime:hitSample`
+fn validate_hit_object(line: &str) -> Result<(), String> {
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() < 5 {
+        return Err(format!("expected at least 5 comma-separated fields, found {}", fields.len()));
+    }
+
+    let x: i32 = fields[0].trim().parse().map_err(|_| format!("invalid x '{}'", fields[0]))?;
+    let time: i32 = fields[2].trim().parse().map_err(|_| format!("invalid time '{}'", fields[2]))?;
+    let kind: i32 = fields[3].trim().parse().map_err(|_| format!("invalid type '{}'", fields[3]))?;
+
+    // Au-delà, osu! ramène la note sur la colonne la plus proche ; ici on refuse
+    if !(0..=512).contains(&x) {
+        return Err(format!("x {} is outside the playfield (0-512)", x));
+    }
+
+    if kind & 128 != 0 {
+        let end = fields
+            .get(5)
+            .and_then(|extras| extras.split(':').next())
+            .ok_or_else(|| "hold note without end time".to_string())?;
+        let end_time: i32 = end.trim().parse().map_err(|_| format!("invalid hold end time '{}'", end))?;
+        if end_time < time {
+            return Err(format!("hold ends ({}) before it starts ({})", end_time, time));
+        }
+    } else if kind & 1 == 0 {
+        return Err(format!("unsupported hit object type {} (mania only has notes and holds)", kind));
+    }
+
+    Ok(())
+}
+
+/// Métadonnées d'un .osu, pour les maps que l'API osu! ne connaît pas
+#[derive(Debug, Clone, Default)]
+pub struct OsuFileMetadata {
+    pub title: String,
+    pub title_unicode: Option<String>,
+    pub artist: String,
+    pub artist_unicode: Option<String>,
+    pub creator: String,
+    pub version: String,
+    pub source: Option<String>,
+    pub tags: Vec<String>,
+    pub beatmap_id: Option<i32>,
+    pub beatmapset_id: Option<i32>,
+    pub mode: i32,
+    pub circle_size: f32,
+    pub overall_difficulty: f32,
+    pub hp_drain_rate: f32,
+    pub approach_rate: f32,
+    /// BPM du premier timing point non hérité
+    pub bpm: f32,
+    pub circle_count: i32,
+    pub hold_count: i32,
+    /// Début du premier et fin du dernier hit object, en ms
+    pub first_object_ms: i32,
+    pub last_object_ms: i32,
+}
+
+/// Lit les sections [General], [Metadata], [Difficulty], [TimingPoints] et [HitObjects].
+///
+/// Tolérant : les valeurs illisibles gardent leur valeur par défaut, la validation
+/// stricte reste le rôle de `validate_osu_file`.
+pub fn parse_osu_metadata(content: &str) -> OsuFileMetadata {
+    let mut meta = OsuFileMetadata::default();
+    let mut approach_rate = None;
+    let mut first_object = None;
+
+    for (_, section, line) in section_lines(content) {
+        match section {
+            "General" | "Metadata" | "Difficulty" => {
+                let Some((key, value)) = line.split_once(':') else { continue };
+                let value = value.trim();
+                let text = || (!value.is_empty()).then(|| value.to_string());
+                match key.trim() {
+                    "Mode" => meta.mode = value.parse().unwrap_or(0),
+                    "Title" => meta.title = value.to_string(),
+                    "TitleUnicode" => meta.title_unicode = text(),
+                    "Artist" => meta.artist = value.to_string(),
+                    "ArtistUnicode" => meta.artist_unicode = text(),
+                    "Creator" => meta.creator = value.to_string(),
+                    "Version" => meta.version = value.to_string(),
+                    "Source" => meta.source = text(),
+                    "Tags" => meta.tags = value.split_whitespace().map(str::to_string).collect(),
+                    "BeatmapID" => meta.beatmap_id = value.parse().ok().filter(|id| *id > 0),
+                    "BeatmapSetID" => meta.beatmapset_id = value.parse().ok().filter(|id| *id > 0),
+                    "CircleSize" => meta.circle_size = value.parse().unwrap_or(0.0),
+                    "OverallDifficulty" => meta.overall_difficulty = value.parse().unwrap_or(0.0),
+                    "HPDrainRate" => meta.hp_drain_rate = value.parse().unwrap_or(0.0),
+                    "ApproachRate" => approach_rate = value.parse().ok(),
+                    _ => {}
+                }
+            }
+            "TimingPoints" if meta.bpm == 0.0 => {
+                let fields: Vec<&str> = line.split(',').collect();
+                let beat_length: f32 = fields.get(1).and_then(|v| v.trim().parse().ok()).unwrap_or(0.0);
+                let uninherited = fields.get(6).map_or(true, |v| v.trim() == "1");
+                if uninherited && beat_length > 0.0 {
+                    meta.bpm = 60000.0 / beat_length;
+                }
+            }
+            "HitObjects" => {
+                let fields: Vec<&str> = line.split(',').collect();
+                let time: i32 = fields.get(2).and_then(|v| v.trim().parse().ok()).unwrap_or(0);
+                let kind: i32 = fields.get(3).and_then(|v| v.trim().parse().ok()).unwrap_or(0);
+                let end_time = if kind & 128 != 0 {
+                    meta.hold_count += 1;
+                    fields
+                        .get(5)
+                        .and_then(|extras| extras.split(':').next())
+                        .and_then(|v| v.trim().parse().ok())
+                        .unwrap_or(time)
+                } else {
+                    meta.circle_count += 1;
+                    time
+                };
+                first_object.get_or_insert(time);
+                meta.last_object_ms = meta.last_object_ms.max(end_time);
+            }
+            _ => {}
+        }
+    }
+
+    // Les vieux fichiers n'ont pas d'ApproachRate : il vaut alors l'OD
+    meta.approach_rate = approach_rate.unwrap_or(meta.overall_difficulty);
+    meta.first_object_ms = first_object.unwrap_or(0);
+    meta
+}
@@ -0,0 +1,579 @@
+//! Requête avancée à la manière de la recherche du client osu! :
+//! `stream>=24 bpm<200 length<180 status=ranked jackspeed<20`.
+//!
+//! Chaque critère `clé opérateur valeur` resserre les `Filters` (`pattern!=chordjack` exclut un
+//! skillset dominant) ; le reste (mots libres, `creator:Evening`) rejoint le terme de recherche.
+
+use crate::helpers::search::SearchField;
+use crate::models::{BeatmapStatus, Filters, MsdPattern};
+use std::fmt;
+
+/// Clés reconnues, pour le message d'erreur
+const KNOWN_KEYS: &str = "overall, stream, jumpstream, handstream, stamina, jackspeed, chordjack, technical, \
+                          bpm, length, od, hp, keys, ln, rate, status, mode, pattern, secondary";
+/// Champs qualifiés de la recherche, acceptés seulement avec `:`
+const SEARCH_KEYS: &str = "artist, title, creator, mapper, source, tag, tags, difficulty, diff, version";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+    /// Début du critère fautif, en caractères depuis le début de la requête
+    pub position: usize,
+    pub token: String,
+}
+
+impl QueryError {
+    fn new(token: &Token, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            position: token.position,
+            token: token.text.clone(),
+        }
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at position {} ({}): {}", self.position, self.token, self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    NotEq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Op {
+    fn is_equality(&self) -> bool {
+        matches!(self, Op::Eq | Op::NotEq)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Overall,
+    Skillset(MsdPattern),
+    Bpm,
+    Length,
+    Od,
+    Hp,
+    Keys,
+    LnRatio,
+    Rate,
+    Status,
+    Mode,
+    Pattern,
+    Secondary,
+}
+
+impl Key {
+    fn parse(key: &str) -> Option<Self> {
+        let key = key.to_lowercase();
+        if let Some(pattern) = MsdPattern::from_name(&key) {
+            return Some(Key::Skillset(pattern));
+        }
+        match key.as_str() {
+            "overall" | "msd" => Some(Key::Overall),
+            "bpm" => Some(Key::Bpm),
+            "length" | "total_time" => Some(Key::Length),
+            "od" => Some(Key::Od),
+            "hp" => Some(Key::Hp),
+            "keys" | "key_count" => Some(Key::Keys),
+            "ln" | "ln_ratio" => Some(Key::LnRatio),
+            "rate" => Some(Key::Rate),
+            "status" => Some(Key::Status),
+            "mode" => Some(Key::Mode),
+            "pattern" => Some(Key::Pattern),
+            "secondary" => Some(Key::Secondary),
+            _ => None,
+        }
+    }
+
+    /// Précision stockée des colonnes décimales : `>`/`<` et `=` s'écartent d'un demi-pas
+    fn step(&self) -> f64 {
+        match self {
+            Key::Overall | Key::Skillset(_) => 0.001,
+            Key::Bpm => 0.01,
+            Key::Od | Key::Hp => 0.1,
+            Key::LnRatio => 0.0001,
+            Key::Rate => 0.1,
+            _ => 1.0,
+        }
+    }
+}
+
+struct Token {
+    text: String,
+    position: usize,
+}
+
+/// Découpe sur les espaces hors guillemets, en gardant les guillemets et la position de chaque mot
+fn split_tokens(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+
+    for (index, c) in input.chars().enumerate() {
+        if c.is_whitespace() && !in_quotes {
+            if !current.is_empty() {
+                tokens.push(Token { text: std::mem::take(&mut current), position: start });
+            }
+            continue;
+        }
+        if current.is_empty() {
+            start = index;
+        }
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        tokens.push(Token { text: current, position: start });
+    }
+
+    tokens
+}
+
+/// `clé`, opérateur, valeur et si l'opérateur est `:` ; `None` pour un mot libre
+fn split_criterion(text: &str) -> Option<(&str, Op, &str, bool)> {
+    let index = text.find(['<', '>', '=', '!', ':'])?;
+    let (key, rest) = text.split_at(index);
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let (op, len, colon) = if rest.starts_with(">=") {
+        (Op::Ge, 2, false)
+    } else if rest.starts_with("<=") {
+        (Op::Le, 2, false)
+    } else if rest.starts_with("!=") {
+        (Op::NotEq, 2, false)
+    } else if rest.starts_with('>') {
+        (Op::Gt, 1, false)
+    } else if rest.starts_with('<') {
+        (Op::Lt, 1, false)
+    } else if rest.starts_with('=') {
+        (Op::Eq, 1, false)
+    } else if rest.starts_with(':') {
+        (Op::Eq, 1, true)
+    } else {
+        return None;
+    };
+
+    Some((key, op, rest[len..].trim_matches('"'), colon))
+}
+
+/// Applique la requête `q` aux filtres : les critères resserrent les bornes déjà présentes
+pub fn apply_query(filters: &mut Filters, q: &str) -> Result<(), QueryError> {
+    let mut free_text = Vec::new();
+
+    for token in split_tokens(q) {
+        let Some((raw_key, op, value, colon)) = split_criterion(&token.text) else {
+            free_text.push(token.text);
+            continue;
+        };
+        let Some(key) = Key::parse(raw_key) else {
+            // `creator:Evening` et les autres champs qualifiés sont l'affaire de la recherche
+            if colon && SearchField::parse(raw_key).is_some() {
+                free_text.push(token.text);
+                continue;
+            }
+            return Err(QueryError::new(
+                &token,
+                format!("unknown key '{}', expected one of: {}, {}", raw_key, KNOWN_KEYS, SEARCH_KEYS),
+            ));
+        };
+        if value.is_empty() {
+            return Err(QueryError::new(&token, format!("missing value for '{}'", raw_key)));
+        }
+        if op == Op::NotEq && key != Key::Pattern {
+            return Err(QueryError::new(&token, "'!=' is only supported for 'pattern'"));
+        }
+
+        apply_criterion(filters, &token, key, op, value)?;
+    }
+
+    if !free_text.is_empty() {
+        let mut terms: Vec<String> = filters.search_term.take().into_iter().collect();
+        terms.extend(free_text);
+        filters.search_term = Some(terms.join(" "));
+    }
+
+    Ok(())
+}
+
+fn apply_criterion(filters: &mut Filters, token: &Token, key: Key, op: Op, value: &str) -> Result<(), QueryError> {
+    match key {
+        Key::Overall => {
+            let (min, max) = decimal_bounds(token, key, op, value)?;
+            tighten(&mut filters.overall_min, &mut filters.overall_max, min, max);
+        }
+        Key::Skillset(pattern) => {
+            let (min, max) = decimal_bounds(token, key, op, value)?;
+            let (current_min, current_max) = filters.skillset_range_mut(pattern);
+            tighten(current_min, current_max, min, max);
+        }
+        Key::Bpm => {
+            let (min, max) = decimal_bounds(token, key, op, value)?;
+            tighten(&mut filters.bpm_min, &mut filters.bpm_max, min, max);
+        }
+        Key::Od => {
+            let (min, max) = decimal_bounds(token, key, op, value)?;
+            tighten(&mut filters.od_min, &mut filters.od_max, min, max);
+        }
+        Key::Hp => {
+            let (min, max) = decimal_bounds(token, key, op, value)?;
+            tighten(&mut filters.hp_min, &mut filters.hp_max, min, max);
+        }
+        Key::LnRatio => {
+            let (min, max) = decimal_bounds(token, key, op, value)?;
+            tighten(&mut filters.ln_ratio_min, &mut filters.ln_ratio_max, min, max);
+        }
+        Key::Length => {
+            let seconds = parse_length(value)
+                .ok_or_else(|| QueryError::new(token, "expected a length in seconds or m:ss"))?;
+            let (min, max) = integer_bounds(op, seconds);
+            tighten(&mut filters.total_time_min, &mut filters.total_time_max, min, max);
+        }
+        Key::Keys => {
+            let keys = value
+                .parse::<i32>()
+                .map_err(|_| QueryError::new(token, "expected a whole number of keys"))?;
+            let (min, max) = integer_bounds(op, keys);
+            tighten(&mut filters.key_count_min, &mut filters.key_count_max, min, max);
+        }
+        Key::Rate => {
+            // `rate=1.2` choisit le rate des MSD, une comparaison en fait une plage
+            if op == Op::Eq {
+                filters.rate = Some(parse_decimal(token, value)?);
+            } else {
+                let (min, max) = decimal_bounds(token, key, op, value)?;
+                tighten(&mut filters.rate_min, &mut filters.rate_max, min, max);
+            }
+        }
+        Key::Status => {
+            require_equality(token, op)?;
+            let status = BeatmapStatus::ALL
+                .into_iter()
+                .find(|status| status.as_str().eq_ignore_ascii_case(value))
+                .ok_or_else(|| QueryError::new(token, format!("unknown status '{}'", value)))?;
+            filters.status = Some(status);
+        }
+        Key::Mode => {
+            require_equality(token, op)?;
+            let mode = match value.to_lowercase().as_str() {
+                "0" | "osu" | "std" => 0,
+                "1" | "taiko" => 1,
+                "2" | "fruits" | "catch" | "ctb" => 2,
+                "3" | "mania" => 3,
+                _ => return Err(QueryError::new(token, format!("unknown mode '{}'", value))),
+            };
+            filters.mode = Some(mode);
+        }
+        Key::Pattern | Key::Secondary => {
+            require_equality(token, op)?;
+            let pattern = MsdPattern::from_name(value)
+                .ok_or_else(|| QueryError::new(token, format!("unknown pattern '{}'", value)))?;
+            match (key, op) {
+                (Key::Pattern, Op::NotEq) => {
+                    if !filters.excluded_patterns.contains(&pattern) {
+                        filters.excluded_patterns.push(pattern);
+                    }
+                }
+                (Key::Pattern, _) => filters.selected_pattern = Some(pattern),
+                _ => filters.secondary_pattern = Some(pattern),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn require_equality(token: &Token, op: Op) -> Result<(), QueryError> {
+    if op.is_equality() {
+        Ok(())
+    } else {
+        Err(QueryError::new(token, "only '=' is supported for this key"))
+    }
+}
+
+fn parse_decimal(token: &Token, value: &str) -> Result<f64, QueryError> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| QueryError::new(token, format!("expected a number, got '{}'", value)))
+}
+
+/// `180` ou `3:00`, en secondes
+fn parse_length(value: &str) -> Option<i32> {
+    match value.split_once(':') {
+        Some((minutes, seconds)) => {
+            let minutes = minutes.parse::<i32>().ok()?;
+            let seconds = seconds.parse::<i32>().ok().filter(|s| (0..60).contains(s))?;
+            minutes.checked_mul(60)?.checked_add(seconds)
+        }
+        None => value.parse::<i32>().ok(),
+    }
+}
+
+/// Bornes d'une comparaison sur une colonne décimale, à un demi-pas de précision près
+fn decimal_bounds(token: &Token, key: Key, op: Op, value: &str) -> Result<(Option<f64>, Option<f64>), QueryError> {
+    let value = parse_decimal(token, value)?;
+    let half_step = key.step() / 2.0;
+    Ok(match op {
+        Op::Eq | Op::NotEq => (Some(value - half_step), Some(value + half_step)),
+        Op::Ge => (Some(value), None),
+        Op::Gt => (Some(value + half_step), None),
+        Op::Le => (None, Some(value)),
+        Op::Lt => (None, Some(value - half_step)),
+    })
+}
+
+fn integer_bounds(op: Op, value: i32) -> (Option<i32>, Option<i32>) {
+    match op {
+        Op::Eq | Op::NotEq => (Some(value), Some(value)),
+        Op::Ge => (Some(value), None),
+        Op::Gt => (Some(value.saturating_add(1)), None),
+        Op::Le => (None, Some(value)),
+        Op::Lt => (None, Some(value.saturating_sub(1))),
+    }
+}
+
+/// Garde la plus stricte de chaque borne
+fn tighten<T: PartialOrd + Copy>(current_min: &mut Option<T>, current_max: &mut Option<T>, min: Option<T>, max: Option<T>) {
+    if let Some(min) = min {
+        *current_min = Some(match *current_min {
+            Some(current) if current > min => current,
+            _ => min,
+        });
+    }
+    if let Some(max) = max {
+        *current_max = Some(match *current_max {
+            Some(current) if current < max => current,
+            _ => max,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::short::complete::query::common::{bind_filter_params, build_where_conditions};
+    use sqlx::{Arguments, Execute};
+
+    fn empty_filters() -> Filters {
+        serde_json::from_str("{}").unwrap()
+    }
+
+    fn parse(q: &str) -> Filters {
+        let mut filters = empty_filters();
+        apply_query(&mut filters, q).unwrap();
+        filters
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("bound not set");
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn strict_bounds_move_half_a_step() {
+        let filters = parse("stream>24 jackspeed<20 bpm>200 od<8");
+
+        assert_close(filters.stream_min, 24.0005);
+        assert_eq!(filters.stream_max, None);
+        assert_close(filters.jackspeed_max, 19.9995);
+        assert_close(filters.bpm_min, 200.005);
+        assert_close(filters.od_max, 7.95);
+    }
+
+    #[test]
+    fn inclusive_bounds_keep_the_value() {
+        let filters = parse("overall>=25 technical<=30 keys>=4 keys<=7");
+
+        assert_close(filters.overall_min, 25.0);
+        assert_close(filters.technical_max, 30.0);
+        assert_eq!(filters.key_count_min, Some(4));
+        assert_eq!(filters.key_count_max, Some(7));
+    }
+
+    #[test]
+    fn equality_spans_half_a_step_on_each_side() {
+        let filters = parse("hp=8 keys=4");
+
+        assert_close(filters.hp_min, 7.95);
+        assert_close(filters.hp_max, 8.05);
+        assert_eq!(filters.key_count_min, Some(4));
+        assert_eq!(filters.key_count_max, Some(4));
+    }
+
+    #[test]
+    fn key_count_equality_builds_where_clause() {
+        let filters = parse("keys=7");
+        let (conditions, param_count) = build_where_conditions(&filters);
+
+        assert_eq!(
+            conditions,
+            vec!["b.key_count >= $1", "b.key_count <= $2", "(m.rate = 1.0 OR m.id IS NULL)"]
+        );
+
+        // Autant de valeurs liées que de paramètres numérotés
+        let mut query = bind_filter_params(sqlx::query(""), &filters);
+        let arguments = query.take_arguments().unwrap().unwrap();
+        assert_eq!(arguments.len(), param_count);
+    }
+
+    #[test]
+    fn bounds_tighten_existing_filters() {
+        let mut filters = empty_filters();
+        filters.bpm_max = Some(180.0);
+        filters.bpm_min = Some(150.0);
+
+        apply_query(&mut filters, "bpm<=200 bpm>=160").unwrap();
+
+        assert_close(filters.bpm_max, 180.0);
+        assert_close(filters.bpm_min, 160.0);
+    }
+
+    #[test]
+    fn length_accepts_seconds_and_minutes() {
+        let filters = parse("length<3:00 length>=90");
+        assert_eq!(filters.total_time_max, Some(179));
+        assert_eq!(filters.total_time_min, Some(90));
+
+        let filters = parse("length=2:05");
+        assert_eq!(filters.total_time_min, Some(125));
+        assert_eq!(filters.total_time_max, Some(125));
+
+        let mut filters = empty_filters();
+        let err = apply_query(&mut filters, "length<3:75").unwrap_err();
+        assert_eq!(err.token, "length<3:75");
+    }
+
+    #[test]
+    fn rate_equality_selects_a_rate_and_comparison_a_range() {
+        let filters = parse("rate=1.2");
+        assert_eq!(filters.rate, Some(1.2));
+        assert_eq!(filters.rate_min, None);
+
+        let filters = parse("rate>1.0 rate<=1.5");
+        assert_eq!(filters.rate, None);
+        assert_close(filters.rate_min, 1.05);
+        assert_close(filters.rate_max, 1.5);
+    }
+
+    #[test]
+    fn unknown_key_reports_position_and_token() {
+        let mut filters = empty_filters();
+        let err = apply_query(&mut filters, "stream>=24  foo>3 bpm<200").unwrap_err();
+
+        assert_eq!(err.position, 12);
+        assert_eq!(err.token, "foo>3");
+        assert!(err.message.contains("unknown key 'foo'"));
+        assert!(err.message.contains("jackspeed"));
+    }
+
+    #[test]
+    fn invalid_values_report_their_token() {
+        for (q, token, message) in [
+            ("stream>abc", "stream>abc", "expected a number"),
+            ("status>ranked", "status>ranked", "only '='"),
+            ("x status=nope", "status=nope", "unknown status"),
+            ("bpm!=180", "bpm!=180", "only supported for 'pattern'"),
+            ("stream>=", "stream>=", "missing value"),
+        ] {
+            let mut filters = empty_filters();
+            let err = apply_query(&mut filters, q).unwrap_err();
+            assert_eq!(err.token, token, "{}", q);
+            assert!(err.message.contains(message), "{}: {}", q, err.message);
+        }
+    }
+
+    #[test]
+    fn positions_count_characters_not_bytes() {
+        let mut filters = empty_filters();
+        let err = apply_query(&mut filters, "été foo>3").unwrap_err();
+
+        assert_eq!(err.position, 4);
+    }
+
+    #[test]
+    fn search_fields_and_free_words_go_to_search_term() {
+        let mut filters = empty_filters();
+        filters.search_term = Some(String::from("camellia"));
+
+        apply_query(&mut filters, "creator:Evening stream>20 \"freedom dive\" <3").unwrap();
+
+        assert_eq!(
+            filters.search_term.as_deref(),
+            Some("camellia creator:Evening \"freedom dive\" <3")
+        );
+        assert_close(filters.stream_min, 20.0005);
+    }
+
+    #[test]
+    fn unknown_colon_keys_are_rejected() {
+        let mut filters = empty_filters();
+        let err = apply_query(&mut filters, "artist:camellia strem:24").unwrap_err();
+
+        assert_eq!(err.token, "strem:24");
+        assert_eq!(err.position, 16);
+        assert!(err.message.contains("unknown key 'strem'"));
+        assert_eq!(filters.search_term, None);
+
+        let filters = parse("Diff:Another tags:tech");
+        assert_eq!(filters.search_term.as_deref(), Some("Diff:Another tags:tech"));
+    }
+
+    #[test]
+    fn status_mode_and_patterns() {
+        let filters = parse("status=Ranked mode=mania pattern:stream secondary=jackspeed");
+
+        assert_eq!(filters.status, Some(BeatmapStatus::Ranked));
+        assert_eq!(filters.mode, Some(3));
+        assert_eq!(filters.selected_pattern, Some(MsdPattern::Stream));
+        assert_eq!(filters.secondary_pattern, Some(MsdPattern::Jackspeed));
+    }
+
+    #[test]
+    fn pattern_inequality_excludes_once() {
+        let filters = parse("pattern!=chordjack pattern!=JackSpeed pattern!=chordjack");
+
+        assert_eq!(filters.excluded_patterns, vec![MsdPattern::Chordjack, MsdPattern::Jackspeed]);
+        assert_eq!(filters.selected_pattern, None);
+    }
+
+    #[test]
+    fn pattern_inequality_extends_excluded_patterns_param() {
+        let mut filters: Filters = serde_json::from_str(r#"{"excluded_patterns": "stamina,chordjack"}"#).unwrap();
+
+        apply_query(&mut filters, "pattern!=chordjack pattern!=technical pattern=stream").unwrap();
+
+        assert_eq!(
+            filters.excluded_patterns,
+            vec![MsdPattern::Stamina, MsdPattern::Chordjack, MsdPattern::Technical]
+        );
+        assert_eq!(filters.selected_pattern, Some(MsdPattern::Stream));
+    }
+
+    #[test]
+    fn pattern_inequality_rejects_other_keys_and_unknown_patterns() {
+        let mut filters = empty_filters();
+        let err = apply_query(&mut filters, "secondary!=stream").unwrap_err();
+        assert_eq!(err.token, "secondary!=stream");
+
+        let err = apply_query(&mut filters, "pattern!=jumptrill").unwrap_err();
+        assert_eq!(err.token, "pattern!=jumptrill");
+        assert!(filters.excluded_patterns.is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! Découpage du terme de recherche : mots libres, cherchés en plein texte,
+//! et champs qualifiés comme `creator:Evening artist:"Camellia"`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Artist,
+    Title,
+    Creator,
+    Source,
+    Tag,
+    Difficulty,
+}
+
+impl SearchField {
+    pub(crate) fn parse(key: &str) -> Option<Self> {
+        match key.to_lowercase().as_str() {
+            "artist" => Some(SearchField::Artist),
+            "title" => Some(SearchField::Title),
+            "creator" | "mapper" => Some(SearchField::Creator),
+            "source" => Some(SearchField::Source),
+            "tag" | "tags" => Some(SearchField::Tag),
+            "difficulty" | "diff" | "version" => Some(SearchField::Difficulty),
+            _ => None,
+        }
+    }
+
+    /// Retourne la condition SQL sécurisée pour ce champ, sur le paramètre `$param`
+    pub fn as_condition(&self, param: usize) -> String {
+        match self {
+            SearchField::Artist => format!("(bs.artist ILIKE ${0} OR bs.artist_unicode ILIKE ${0})", param),
+            SearchField::Title => format!("(bs.title ILIKE ${0} OR bs.title_unicode ILIKE ${0})", param),
+            SearchField::Creator => format!("bs.creator ILIKE ${}", param),
+            SearchField::Source => format!("bs.source ILIKE ${}", param),
+            // Tags stockés en minuscules : l'égalité d'éléments passe par l'index GIN
+            SearchField::Tag => format!("bs.tags @> ARRAY[${}]::text[]", param),
+            SearchField::Difficulty => format!("b.difficulty ILIKE ${}", param),
+        }
+    }
+
+    /// Valeur liée : motif ILIKE de sous-chaîne, ou tag entier en minuscules
+    pub fn bind_value(&self, value: &str) -> String {
+        if *self == SearchField::Tag {
+            return value.to_lowercase();
+        }
+        let escaped = value
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        format!("%{}%", escaped)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Mots libres en syntaxe tsquery (préfixes, phrases entre guillemets), `None` sans mot libre
+    pub text: Option<String>,
+    pub fields: Vec<(SearchField, String)>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut groups = Vec::new();
+        let mut fields = Vec::new();
+
+        for (token, quoted) in split_tokens(input) {
+            if let Some((key, value)) = token.split_once(':') {
+                if let Some(field) = SearchField::parse(key) {
+                    if !value.trim().is_empty() {
+                        fields.push((field, value.trim().to_string()));
+                    }
+                    continue;
+                }
+            }
+
+            // Seuls les caractères de mots passent : aucun opérateur tsquery ne vient de l'utilisateur
+            let words: Vec<String> = token
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(|w| format!("{}:*", w.to_lowercase()))
+                .collect();
+            if words.is_empty() {
+                continue;
+            }
+            if quoted {
+                groups.push(format!("({})", words.join(" <-> ")));
+            } else {
+                groups.extend(words);
+            }
+        }
+
+        Self {
+            text: (!groups.is_empty()).then(|| groups.join(" & ")),
+            fields,
+        }
+    }
+}
+
+/// Découpe sur les espaces hors guillemets ; les guillemets sont retirés et signalés
+fn split_tokens(input: &str) -> Vec<(String, bool)> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push((std::mem::take(&mut current), quoted));
+                }
+                quoted = false;
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push((current, quoted));
+    }
+
+    tokens
+}
@@ -0,0 +1,52 @@
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Multipart, Request},
+    http::{StatusCode, header::CONTENT_TYPE},
+};
+
+/// Lit un fichier envoyé brut (body) ou en multipart (champ `field`), borné à `max_bytes`
+pub async fn read_upload(
+    request: Request,
+    field_name: &str,
+    max_bytes: usize,
+) -> Result<Bytes, (StatusCode, String)> {
+    let is_multipart = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"));
+
+    let bytes = if is_multipart {
+        let mut multipart = Multipart::from_request(request, &())
+            .await
+            .map_err(|e| (e.status(), e.body_text()))?;
+
+        let mut file = None;
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|e| (e.status(), e.body_text()))?
+        {
+            if field.name() == Some(field_name) {
+                file = Some(field.bytes().await.map_err(|e| (e.status(), e.body_text()))?);
+                break;
+            }
+        }
+        file.ok_or_else(|| {
+            (StatusCode::BAD_REQUEST, format!("Missing multipart field '{}'", field_name))
+        })?
+    } else {
+        Bytes::from_request(request, &())
+            .await
+            .map_err(|e| (e.status(), e.body_text()))?
+    };
+
+    if bytes.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Empty body".to_string()));
+    }
+    if bytes.len() > max_bytes {
+        return Err((StatusCode::PAYLOAD_TOO_LARGE, "File too large".to_string()));
+    }
+
+    Ok(bytes)
+}
@@ -10,6 +10,7 @@
 //! - Configuration CORS
 //! - Gestion des erreurs
 
+mod cli;
 mod config;
 mod db;
 mod handlers;
@@ -25,6 +26,7 @@ use crate::middleware::anti_kiddie::{anti_kiddie_middleware, cleanup_old_entries
 use crate::middleware::cache::{cache_middleware, warm_cache, cleanup_cache_stats};
 use crate::services::beatmap_queue::processor::BeatmapProcessor;
 use crate::services::osu_api::OsuApiService;
+use crate::services::osu_file_store::OsuFileStore;
 use crate::services::status::start_background_metrics_task;
 use axum::{middleware::from_fn, Router};
 use std::net::SocketAddr;
@@ -47,13 +49,25 @@ async fn main() {
     )
     .await
     .expect("Failed to initialize OsuApiService");
+    OsuFileStore::initialize(&config.storage.osu_files_dir)
+        .expect("Failed to initialize osu file store");
+    info!("Osu file store ready at {}", config.storage.osu_files_dir);
+
+    // Sous-commande CLI : pas de serveur ni de workers
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        BeatmapProcessor::initialize(db.clone());
+        let code = cli::run(&args).await.unwrap_or(0);
+        std::process::exit(code);
+    }
+
     start_background_metrics_task(db.clone(), config.clone()).await;
     info!("Background metrics task started (5-minute intervals)");
 
     BeatmapProcessor::initialize(db.clone());
     info!("BeatmapProcessor initialized");
-    BeatmapProcessor::instance().start_processing_thread();
-    info!("BeatmapProcessor thread started");
+    BeatmapProcessor::instance().start_processing_thread(&config.queue);
+    info!("BeatmapProcessor thread started ({} workers)", config.queue.workers);
 
     // Démarrer les tâches de nettoyage
     tokio::spawn(cleanup_old_entries());
@@ -67,7 +81,7 @@ async fn main() {
     info!("🔥 Cache warming task started");
 
     let app = Router::new()
-        .merge(routes::create_router(db))
+        .merge(routes::create_router(db, &config))
         .layer(
             ServiceBuilder::new()
                 .layer(from_fn(cache_middleware))        // Cache en premier (plus proche de la réponse)
@@ -86,6 +100,40 @@ async fn main() {
         tokio::net::TcpListener::bind(addr).await.unwrap(),
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
+    .with_graceful_shutdown(shutdown_signal())
     .await
     .unwrap();
+
+    // Laisser les workers terminer les beatmaps en cours avant de quitter
+    BeatmapProcessor::stop_processing_thread().await;
+    info!("Shutdown complete");
+}
+
+/// Attend Ctrl+C ou SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    info!("Shutdown signal received, stopping server");
+    // Sans attendre la fin des connexions : les workers arrêtent de réclamer, les flux SSE se ferment
+    BeatmapProcessor::request_shutdown();
 }
@@ -0,0 +1,44 @@
+use crate::config::AdminConfig;
+use axum::{
+    extract::{Request, State},
+    http::{StatusCode, header::AUTHORIZATION},
+    middleware::Next,
+    response::Response,
+};
+use tracing::warn;
+
+/// Vérifie le header `Authorization: Bearer <ADMIN_TOKEN>` sur les routes admin
+pub async fn admin_auth_middleware(
+    State(admin): State<AdminConfig>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    // Pas de token configuré : les routes admin n'existent pas
+    let Some(expected) = admin.token.as_deref() else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+
+    let provided = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => {
+            Ok(next.run(request).await)
+        }
+        _ => {
+            warn!("Unauthorized admin access to {}", request.uri());
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
+}
+
+/// Comparaison sans court-circuit pour ne pas révéler le token par le timing
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -28,6 +28,9 @@ lazy_static::lazy_static! {
         Arc::new(Mutex::new(HashMap::new()));
 }
 
+/// Préfixe des routes admin légitimes, protégées par token
+const ADMIN_API_PREFIX: &str = "/api/admin/";
+
 /// Patterns d'attaques communes que les script kiddies utilisent
 const ATTACK_PATTERNS: &[&str] = &[
     // SQL Injection classique
@@ -187,10 +190,11 @@ fn record_attack_attempt(ip: &str) -> Duration {
 
 /// Vérifie si la requête contient des patterns d'attaque
 fn contains_attack_patterns(request: &Request) -> bool {
-    // Vérifier l'URL complète
+    // Vérifier l'URL complète (le préfixe de nos propres routes admin ne compte pas)
     let uri = request.uri().to_string().to_lowercase();
+    let scanned_uri = uri.strip_prefix(ADMIN_API_PREFIX).unwrap_or(&uri);
     for pattern in ATTACK_PATTERNS {
-        if uri.contains(&pattern.to_lowercase()) {
+        if scanned_uri.contains(&pattern.to_lowercase()) {
             return true;
         }
     }
@@ -138,7 +138,7 @@ impl CacheStore {
             CacheType::GlobalStats
         } else if path == "/api/beatmap" {
             CacheType::FilteredQueries
-        } else if path.starts_with("/api/beatmapset/") {
+        } else if path.starts_with("/api/beatmapset/") || is_beatmap_detail_route(path) {
             CacheType::IndividualBeatmaps
         } else if path.starts_with("/api/pending_beatmap/status/") {
             CacheType::PendingStatus
@@ -179,6 +179,14 @@ impl CacheStore {
             // On garde les beatmaps individuels car ils changent moins souvent
         }
     }
+
+    /// Invalide les caches qui exposent des MSD, remplacées par un recalcul
+    pub fn invalidate_msd_caches(&self) {
+        info!("🗑️ Invalidating MSD caches after recalculation");
+        self.global_stats.invalidate_all();
+        self.filtered_queries.invalidate_all();
+        self.individual_beatmaps.invalidate_all();
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -221,11 +229,17 @@ fn should_cache_route(method: &Method, path: &str) -> bool {
         p if p == "/api/beatmap/count" => true,
         p if p == "/api/beatmap" => true,
         p if p.starts_with("/api/beatmapset/") => true,
+        p if is_beatmap_detail_route(p) => true,
         p if p.starts_with("/api/pending_beatmap/status/") => true,
         _ => false,
     }
 }
 
+/// `/api/beatmap/{osu_id}/density` et `/msd` : ne changent qu'au recalcul des MSD, qui vide ce cache
+fn is_beatmap_detail_route(path: &str) -> bool {
+    path.starts_with("/api/beatmap/") && (path.ends_with("/density") || path.ends_with("/msd"))
+}
+
 /// Vérifie si une route ne doit jamais être cachée (ex: random)
 fn should_never_cache(path: &str) -> bool {
     path.contains("/random") || path.contains("/health")
@@ -346,6 +360,11 @@ pub fn get_cache_stats() -> CacheStats {
     CACHE_STORE.get_stats()
 }
 
+/// Vide les réponses contenant des MSD (recalcul par une nouvelle version de MinaCalc)
+pub fn invalidate_msd_caches() {
+    CACHE_STORE.invalidate_msd_caches();
+}
+
 /// Fonction pour pré-chauffer le cache avec les routes critiques
 pub async fn warm_cache() {
     info!("🔥 Starting cache warming...");
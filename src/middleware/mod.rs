@@ -1,3 +1,4 @@
 pub mod logging;
 pub mod anti_kiddie;
+pub mod admin_auth;
 pub mod cache;
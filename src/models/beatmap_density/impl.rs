@@ -0,0 +1,20 @@
+use crate::models::beatmap_density::query::{find_by_osu_id, upsert};
+use crate::models::beatmap_density::types::{BeatmapDensity, DensityTimeline};
+use sqlx::{PgExecutor, PgPool};
+
+impl BeatmapDensity {
+    pub async fn upsert<'e, E>(
+        executor: E,
+        beatmap_id: i32,
+        timeline: &DensityTimeline,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        upsert(executor, beatmap_id, timeline).await
+    }
+
+    pub async fn find_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<Self>, sqlx::Error> {
+        find_by_osu_id(pool, osu_id).await
+    }
+}
@@ -0,0 +1,22 @@
+use crate::models::beatmap_density::types::{BeatmapDensity, DensityTimeline};
+use sqlx::{Error as SqlxError, PgPool, types::Json};
+
+pub async fn find_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<BeatmapDensity>, SqlxError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT d.beatmap_id, b.osu_id, d.timeline as "timeline: Json<DensityTimeline>"
+        FROM beatmap_density d
+        JOIN beatmap b ON b.id = d.beatmap_id
+        WHERE b.osu_id = $1
+        "#,
+        osu_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|r| BeatmapDensity {
+        beatmap_id: r.beatmap_id,
+        osu_id: r.osu_id,
+        timeline: r.timeline.0,
+    }))
+}
@@ -0,0 +1,5 @@
+pub mod by_osu_id;
+pub mod upsert;
+
+pub use by_osu_id::*;
+pub use upsert::*;
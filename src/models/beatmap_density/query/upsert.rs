@@ -0,0 +1,30 @@
+use crate::models::beatmap_density::types::DensityTimeline;
+use sqlx::{Error as SqlxError, PgExecutor, types::Json};
+
+/// Enregistre la timeline d'une beatmap, en remplaçant l'éventuelle précédente
+pub async fn upsert<'e, E>(
+    executor: E,
+    beatmap_id: i32,
+    timeline: &DensityTimeline,
+) -> Result<(), SqlxError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query!(
+        r#"
+        INSERT INTO beatmap_density (beatmap_id, window_ms, timeline)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (beatmap_id) DO UPDATE
+        SET window_ms = EXCLUDED.window_ms,
+            timeline = EXCLUDED.timeline,
+            updated_at = now()
+        "#,
+        beatmap_id,
+        timeline.window_ms,
+        Json(timeline) as _
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Largeur d'une fenêtre de la timeline, en ms
+pub const DENSITY_WINDOW_MS: i32 = 500;
+
+/// Densité d'une map par fenêtre de `window_ms`, à partir de `start_ms`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DensityTimeline {
+    pub window_ms: i32,
+    /// Début de la première fenêtre : la première note de la map
+    pub start_ms: i32,
+    pub key_count: i32,
+    /// Notes par seconde dans chaque fenêtre
+    pub nps: Vec<f32>,
+    /// Notes par ligne dans chaque fenêtre (1 = que des notes seules)
+    pub chord_density: Vec<f32>,
+    /// Nombre de notes par colonne dans chaque fenêtre
+    pub column_load: Vec<Vec<u16>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BeatmapDensity {
+    pub beatmap_id: i32,
+    pub osu_id: Option<i32>,
+    #[serde(flatten)]
+    pub timeline: DensityTimeline,
+}
@@ -0,0 +1,56 @@
+//! Curseur opaque de la liste filtrée : position du dernier beatmapset renvoyé.
+
+use crate::models::{Filters, SortBy, SortDir};
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Tri de la requête qui a produit le curseur
+    pub sort_by: Option<SortBy>,
+    pub sort_dir: SortDir,
+    /// Clé de tri du dernier beatmapset, en texte (`None` : clé nulle, ou pas de tri)
+    pub key: Option<String>,
+    /// Id en base du dernier beatmapset
+    pub id: i32,
+}
+
+impl Cursor {
+    /// JSON encodé en hexadécimal : sûr dans une URL, sans promesse de format pour les clients
+    pub fn encode(&self) -> String {
+        serde_json::to_vec(self)
+            .unwrap_or_default()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        if value.len() % 2 != 0 {
+            return None;
+        }
+        let bytes = (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Un curseur n'est valable qu'avec le tri qui l'a produit
+    pub fn matches(&self, filters: &Filters) -> bool {
+        self.sort_by == filters.resolved_sort_by() && self.sort_dir == filters.resolved_sort_dir()
+    }
+}
+
+/// Désérialise `?cursor=` : un curseur illisible rejette la requête
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Cursor>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(value) if value.is_empty() => Ok(None),
+        Some(value) => Cursor::decode(&value)
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom("invalid cursor")),
+    }
+}
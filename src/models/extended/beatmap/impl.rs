@@ -1,26 +1,61 @@
 use crate::models::extended::beatmap::query::{
-    Insert, exists_by_checksum, find_by_id, get_beatmapset_id,
+    Insert, beatmapset_osu_id_by_checksum, exists_by_checksum, existing_checksums, find_by_id,
+    find_by_osu_id, get_beatmapset_id, count_outdated_msd, find_outdated_msd,
 };
 use crate::models::extended::beatmap::types::BeatmapExtended;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 impl BeatmapExtended {
-    pub async fn insert_into_db(&self, pool: &PgPool) -> Result<i32, sqlx::Error> {
-        self.insert(pool).await
+    pub async fn insert_into_db<'e, E>(&self, executor: E) -> Result<Option<i32>, sqlx::Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        self.insert(executor).await
     }
 
     pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<Self>, sqlx::Error> {
         find_by_id(pool, id).await
     }
 
+    pub async fn find_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<Self>, sqlx::Error> {
+        find_by_osu_id(pool, osu_id).await
+    }
+
     pub async fn exists_by_checksum(pool: &PgPool, checksum: &str) -> Result<bool, sqlx::Error> {
         exists_by_checksum(pool, checksum).await
     }
 
+    pub async fn existing_checksums(
+        pool: &PgPool,
+        checksums: &[String],
+    ) -> Result<Vec<String>, sqlx::Error> {
+        existing_checksums(pool, checksums).await
+    }
+
     pub async fn get_beatmapset_id(
         pool: &PgPool,
         beatmap_id: i32,
     ) -> Result<Option<i32>, sqlx::Error> {
         get_beatmapset_id(pool, beatmap_id).await
     }
+
+    pub async fn find_outdated_msd(
+        pool: &PgPool,
+        calc_version: i32,
+        after_id: i32,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        find_outdated_msd(pool, calc_version, after_id, limit).await
+    }
+
+    pub async fn count_outdated_msd(pool: &PgPool, calc_version: i32) -> Result<i64, sqlx::Error> {
+        count_outdated_msd(pool, calc_version).await
+    }
+
+    pub async fn beatmapset_osu_id_by_checksum(
+        pool: &PgPool,
+        checksum: &str,
+    ) -> Result<Option<Option<i32>>, sqlx::Error> {
+        beatmapset_osu_id_by_checksum(pool, checksum).await
+    }
 }
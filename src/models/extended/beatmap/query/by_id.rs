@@ -13,6 +13,18 @@ pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<BeatmapExtended
     .await
 }
 
+pub async fn find_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<BeatmapExtended>, SqlxError> {
+    sqlx::query_as!(
+        BeatmapExtended,
+        r#"
+        SELECT * FROM beatmap WHERE osu_id = $1
+        "#,
+        osu_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
 pub async fn get_beatmapset_id(pool: &PgPool, beatmap_id: i32) -> Result<Option<i32>, SqlxError> {
     let row = sqlx::query!(
         "SELECT beatmapset_id FROM beatmap WHERE id = $1",
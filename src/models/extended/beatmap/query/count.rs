@@ -1,4 +1,5 @@
 use sqlx::{Error as SqlxError, PgPool};
+use std::collections::HashMap;
 
 /// Compte le nombre total de beatmaps dans la base de données
 pub async fn count_beatmaps(pool: &PgPool) -> Result<Option<i64>, SqlxError> {
@@ -16,11 +17,15 @@ pub async fn count_beatmaps(pool: &PgPool) -> Result<Option<i64>, SqlxError> {
     Ok(row.count)
 }
 
-/// Récupère toutes les statistiques en une seule requête optimisée
-pub async fn get_all_stats(pool: &PgPool) -> Result<(Option<i64>, Option<i64>, std::collections::HashMap<String, u64>), SqlxError> {
-    use std::collections::HashMap;
-    use serde_json::Value;
+/// Nombre de beatmaps par skillset dominant, au rate 1.0
+#[derive(Debug, Default)]
+pub struct PatternCounts {
+    pub primary: HashMap<String, u64>,
+    pub secondary: HashMap<String, u64>,
+}
 
+/// Récupère toutes les statistiques en une seule requête optimisée
+pub async fn get_all_stats(pool: &PgPool) -> Result<(Option<i64>, Option<i64>, PatternCounts), SqlxError> {
     // Requête unique optimisée qui récupère tout en une fois
     let rows = sqlx::query!(
         r#"
@@ -30,19 +35,24 @@ pub async fn get_all_stats(pool: &PgPool) -> Result<(Option<i64>, Option<i64>, s
                 (SELECT COALESCE(reltuples::bigint, 0) FROM pg_class WHERE relname = 'beatmapset') as beatmapset_count
         ),
         pattern_stats AS (
-            SELECT main_pattern, COUNT(*) as count
+            SELECT 'primary' as kind, primary_pattern as pattern, COUNT(*) as count
             FROM msd
-            WHERE rate = 1.0 AND main_pattern IS NOT NULL
-            GROUP BY main_pattern
-            ORDER BY count DESC
+            WHERE rate = 1.0 AND primary_pattern IS NOT NULL
+            GROUP BY primary_pattern
+            UNION ALL
+            SELECT 'secondary' as kind, secondary_pattern as pattern, COUNT(*) as count
+            FROM msd
+            WHERE rate = 1.0 AND secondary_pattern IS NOT NULL
+            GROUP BY secondary_pattern
         )
         SELECT 
             s.beatmap_count,
             s.beatmapset_count,
-            p.main_pattern,
+            p.kind,
+            p.pattern,
             p.count as pattern_count
         FROM stats s
-        CROSS JOIN pattern_stats p
+        LEFT JOIN pattern_stats p ON true
         "#
     )
     .fetch_all(pool)
@@ -50,23 +60,15 @@ pub async fn get_all_stats(pool: &PgPool) -> Result<(Option<i64>, Option<i64>, s
 
     let mut beatmap_count = None;
     let mut beatmapset_count = None;
-    let mut patterns: HashMap<String, u64> = HashMap::new();
+    let mut patterns = PatternCounts::default();
 
     for row in rows {
         beatmap_count = Some(row.beatmap_count.unwrap_or(0));
         beatmapset_count = Some(row.beatmapset_count.unwrap_or(0));
-        
-        if let Some(pattern_json) = row.main_pattern {
-            if let Ok(json_value) = serde_json::from_str::<Value>(&pattern_json) {
-                if let Some(array) = json_value.as_array() {
-                    if let Some(first_pattern) = array.first() {
-                        if let Some(pattern_str) = first_pattern.as_str() {
-                            *patterns.entry(pattern_str.to_string()).or_insert(0) += row.pattern_count.unwrap_or(0) as u64;
-                        }
-                    }
-                }
-            }
-        }
+
+        let (Some(kind), Some(pattern)) = (row.kind, row.pattern) else { continue };
+        let counts = if kind == "primary" { &mut patterns.primary } else { &mut patterns.secondary };
+        counts.insert(pattern, row.pattern_count.unwrap_or(0) as u64);
     }
 
     Ok((beatmap_count, beatmapset_count, patterns))
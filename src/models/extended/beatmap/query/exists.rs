@@ -9,3 +9,36 @@ pub async fn exists_by_checksum(pool: &PgPool, checksum: &str) -> Result<bool, S
     .await?;
     Ok(row.exists.unwrap_or(false))
 }
+
+/// Retourne, parmi les checksums donnés, ceux déjà présents en base
+pub async fn existing_checksums(
+    pool: &PgPool,
+    checksums: &[String],
+) -> Result<Vec<String>, SqlxError> {
+    let rows = sqlx::query_scalar!(
+        r#"SELECT file_md5 as "file_md5!" FROM beatmap WHERE file_md5 = ANY($1)"#,
+        checksums
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Si le checksum est en base, retourne l'id osu! de son beatmapset (`Some(None)` s'il n'en a pas)
+pub async fn beatmapset_osu_id_by_checksum(
+    pool: &PgPool,
+    checksum: &str,
+) -> Result<Option<Option<i32>>, SqlxError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT bs.osu_id as "osu_id?"
+        FROM beatmap b
+        LEFT JOIN beatmapset bs ON bs.id = b.beatmapset_id
+        WHERE b.file_md5 = $1
+        "#,
+        checksum
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|r| r.osu_id))
+}
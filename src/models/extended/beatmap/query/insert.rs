@@ -1,23 +1,31 @@
 use crate::models::extended::beatmap::types::BeatmapExtended;
 use async_trait::async_trait;
-use sqlx::{Error as SqlxError, PgPool};
+use sqlx::{Error as SqlxError, PgExecutor};
 
 #[async_trait]
 pub trait Insert {
-    async fn insert(&self, pool: &PgPool) -> Result<i32, SqlxError>;
+    /// `None` si la beatmap existe déjà (même osu_id ou même md5)
+    async fn insert<'e, E>(&self, executor: E) -> Result<Option<i32>, SqlxError>
+    where
+        E: PgExecutor<'e>;
 }
 
 #[async_trait]
 impl Insert for BeatmapExtended {
-    async fn insert(&self, pool: &PgPool) -> Result<i32, SqlxError> {
+    async fn insert<'e, E>(&self, executor: E) -> Result<Option<i32>, SqlxError>
+    where
+        E: PgExecutor<'e>,
+    {
         let row = sqlx::query!(
             r#"
             INSERT INTO beatmap (
                 osu_id, beatmapset_id, difficulty, difficulty_rating,
                 count_circles, count_sliders, count_spinners, max_combo,
                 drain_time, total_time, bpm, cs, ar, od, hp, mode,
-                status, file_md5, file_path
-            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,$18,$19)
+                status, file_md5, file_path, key_count, avg_nps, peak_nps,
+                ln_count, ln_ratio, avg_ln_length
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,$18,$19,$20,$21,$22,$23,$24,$25)
+            ON CONFLICT DO NOTHING
             RETURNING id
             "#,
             self.osu_id,
@@ -38,11 +46,17 @@ impl Insert for BeatmapExtended {
             self.mode,
             self.status,
             self.file_md5,
-            self.file_path
+            self.file_path,
+            self.key_count,
+            self.avg_nps,
+            self.peak_nps,
+            self.ln_count,
+            self.ln_ratio,
+            self.avg_ln_length
         )
-        .fetch_one(pool)
+        .fetch_optional(executor)
         .await?;
 
-        Ok(row.id)
+        Ok(row.map(|row| row.id))
     }
 }
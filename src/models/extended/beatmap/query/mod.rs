@@ -2,10 +2,12 @@ pub mod by_id;
 pub mod count;
 pub mod exists;
 pub mod insert;
+pub mod outdated;
 pub mod search;
 
 pub use by_id::*;
 pub use count::*;
 pub use exists::*;
 pub use insert::*;
+pub use outdated::*;
 pub use search::*;
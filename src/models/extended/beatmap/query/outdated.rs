@@ -0,0 +1,44 @@
+use crate::models::extended::beatmap::types::BeatmapExtended;
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Beatmaps dont au moins une ligne MSD vient d'une autre version du calculateur,
+/// par id croissant à partir de `after_id` exclu
+pub async fn find_outdated_msd(
+    pool: &PgPool,
+    calc_version: i32,
+    after_id: i32,
+    limit: i64,
+) -> Result<Vec<BeatmapExtended>, SqlxError> {
+    sqlx::query_as!(
+        BeatmapExtended,
+        r#"
+        SELECT b.* FROM beatmap b
+        WHERE b.id > $2
+          AND EXISTS (
+              SELECT 1 FROM msd m
+              WHERE m.beatmap_id = b.id AND m.calc_version IS DISTINCT FROM $1
+          )
+        ORDER BY b.id
+        LIMIT $3
+        "#,
+        calc_version,
+        after_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn count_outdated_msd(pool: &PgPool, calc_version: i32) -> Result<i64, SqlxError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT COUNT(DISTINCT beatmap_id) as "count!"
+        FROM msd
+        WHERE calc_version IS DISTINCT FROM $1
+        "#,
+        calc_version
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(row.count)
+}
@@ -1,12 +1,14 @@
 use bigdecimal::BigDecimal;
 use chrono::NaiveDateTime;
-use rosu_v2::model::beatmap::BeatmapExtended as BmExtended;
+use rosu_v2::model::{GameMode, beatmap::BeatmapExtended as BmExtended};
 use serde::{Deserialize, Serialize};
 
 use crate::helpers::{
     beatmap::{build_file_path, rank_status_to_string},
     common::from_f32,
+    osu_file::OsuFileMetadata,
 };
+use crate::services::msd_calculator::ManiaChart;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BeatmapExtended {
@@ -30,6 +32,15 @@ pub struct BeatmapExtended {
     pub status: String,
     pub file_md5: String,
     pub file_path: String,
+    /// Nombre de colonnes en mania
+    pub key_count: Option<i32>,
+    pub avg_nps: Option<BigDecimal>,
+    pub peak_nps: Option<BigDecimal>,
+    pub ln_count: Option<i32>,
+    /// Part des LN parmi toutes les notes, entre 0 et 1
+    pub ln_ratio: Option<BigDecimal>,
+    /// Durée moyenne d'une LN, en ms
+    pub avg_ln_length: Option<i32>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
 }
@@ -57,8 +68,65 @@ impl From<BmExtended> for BeatmapExtended {
             status: rank_status_to_string(&b.status),
             file_md5: b.checksum.unwrap_or_default(),
             file_path: build_file_path(b.map_id),
+            key_count: (b.mode == GameMode::Mania).then(|| b.cs.round() as i32),
+            avg_nps: None,
+            peak_nps: None,
+            ln_count: None,
+            ln_ratio: None,
+            avg_ln_length: None,
             created_at: None,
             updated_at: None,
         }
     }
 }
+
+impl BeatmapExtended {
+    /// Beatmap locale (sans osu_id), construite depuis un .osu ; le fichier reste dans le cache disque
+    pub fn from_osu_file(meta: &OsuFileMetadata, md5: &str) -> Self {
+        let total_ms = meta.last_object_ms.max(0);
+        let drain_ms = (meta.last_object_ms - meta.first_object_ms).max(0);
+
+        Self {
+            id: 0,
+            osu_id: None,
+            beatmapset_id: None,
+            difficulty: meta.version.clone(),
+            difficulty_rating: from_f32(0.0),
+            count_circles: meta.circle_count,
+            count_sliders: meta.hold_count,
+            count_spinners: 0,
+            max_combo: 0,
+            drain_time: drain_ms / 1000,
+            total_time: total_ms / 1000,
+            bpm: from_f32(meta.bpm),
+            cs: from_f32(meta.circle_size),
+            ar: from_f32(meta.approach_rate),
+            od: from_f32(meta.overall_difficulty),
+            hp: from_f32(meta.hp_drain_rate),
+            mode: meta.mode,
+            status: "local".to_string(),
+            file_md5: md5.to_string(),
+            file_path: format!("local:{}", md5),
+            key_count: (meta.mode == 3).then(|| meta.circle_size.round() as i32),
+            avg_nps: None,
+            peak_nps: None,
+            ln_count: None,
+            ln_ratio: None,
+            avg_ln_length: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    /// Nombre de colonnes, densité et statistiques LN lus depuis le .osu, qui font foi sur l'API
+    pub fn set_chart_stats(&mut self, chart: &ManiaChart) {
+        let density = chart.density();
+        let ln = chart.ln_stats();
+        self.key_count = Some(chart.key_count as i32);
+        self.avg_nps = Some(from_f32(density.avg_nps));
+        self.peak_nps = Some(from_f32(density.peak_nps));
+        self.ln_count = Some(ln.ln_count as i32);
+        self.ln_ratio = Some(from_f32(ln.ln_ratio));
+        self.avg_ln_length = Some(ln.avg_ln_length.round() as i32);
+    }
+}
@@ -2,11 +2,14 @@ use crate::models::extended::beatmapset::query::{
     Insert, exists_by_osu_id, find_all, find_by_id, find_by_osu_id, search,
 };
 use crate::models::extended::beatmapset::types::BeatmapsetExtended;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 impl BeatmapsetExtended {
-    pub async fn insert_into_db(&self, pool: &PgPool) -> Result<i32, sqlx::Error> {
-        self.insert(pool).await
+    pub async fn insert_into_db<'e, E>(&self, executor: E) -> Result<i32, sqlx::Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        self.insert(executor).await
     }
 
     pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<Self>, sqlx::Error> {
@@ -1,15 +1,20 @@
 use crate::models::extended::beatmapset::types::BeatmapsetExtended;
 use async_trait::async_trait;
-use sqlx::{Error as SqlxError, PgPool};
+use sqlx::{Error as SqlxError, PgExecutor};
 
 #[async_trait]
 pub trait Insert {
-    async fn insert(&self, pool: &PgPool) -> Result<i32, SqlxError>;
+    async fn insert<'e, E>(&self, executor: E) -> Result<i32, SqlxError>
+    where
+        E: PgExecutor<'e>;
 }
 
 #[async_trait]
 impl Insert for BeatmapsetExtended {
-    async fn insert(&self, pool: &PgPool) -> Result<i32, SqlxError> {
+    async fn insert<'e, E>(&self, executor: E) -> Result<i32, SqlxError>
+    where
+        E: PgExecutor<'e>,
+    {
         let row = sqlx::query!(
             r#"
             INSERT INTO beatmapset (
@@ -51,7 +56,7 @@ impl Insert for BeatmapsetExtended {
             self.preview_url.as_deref(),
             self.osu_file_url.as_deref()
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(row.id)
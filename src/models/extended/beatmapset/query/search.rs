@@ -12,7 +12,10 @@ pub async fn search(
     sqlx::query_as!(
         BeatmapsetExtended,
         r#"
-        SELECT * FROM beatmapset
+        SELECT id, osu_id, artist, artist_unicode, title, title_unicode, creator, source, tags,
+               has_video, has_storyboard, is_explicit, is_featured, cover_url, preview_url,
+               osu_file_url, created_at, updated_at
+        FROM beatmapset
         WHERE artist ILIKE $1 OR artist_unicode ILIKE $1 OR title ILIKE $1 OR title_unicode ILIKE $1 OR creator ILIKE $1
         ORDER BY created_at DESC
         LIMIT $2 OFFSET $3
@@ -34,7 +37,12 @@ pub async fn find_all(
     let offset = offset.unwrap_or(0);
     sqlx::query_as!(
         BeatmapsetExtended,
-        "SELECT * FROM beatmapset ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        r#"
+        SELECT id, osu_id, artist, artist_unicode, title, title_unicode, creator, source, tags,
+               has_video, has_storyboard, is_explicit, is_featured, cover_url, preview_url,
+               osu_file_url, created_at, updated_at
+        FROM beatmapset ORDER BY created_at DESC LIMIT $1 OFFSET $2
+        "#,
         limit,
         offset
     )
@@ -1,3 +1,4 @@
+use crate::helpers::osu_file::OsuFileMetadata;
 use chrono::NaiveDateTime;
 use rosu_v2::model::beatmap::BeatmapsetExtended as BmsetExtended;
 use serde::{Deserialize, Serialize};
@@ -35,7 +36,9 @@ impl From<BmsetExtended> for BeatmapsetExtended {
             title_unicode: Some(b.title_unicode.unwrap_or("Unknown".to_string())),
             creator: b.creator_name.to_string(),
             source: Some(b.source.to_string()),
-            tags: None,
+            // En minuscules pour la recherche `tag:` (égalité via l'index GIN)
+            tags: Some(b.tags.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>())
+                .filter(|tags| !tags.is_empty()),
             has_video: b.video,
             has_storyboard: b.storyboard,
             is_explicit: b.nsfw,
@@ -48,3 +51,29 @@ impl From<BmsetExtended> for BeatmapsetExtended {
         }
     }
 }
+
+impl BeatmapsetExtended {
+    /// Beatmapset local (sans osu_id), construit depuis les métadonnées d'un .osu
+    pub fn from_osu_file(meta: &OsuFileMetadata) -> Self {
+        Self {
+            id: 0,
+            osu_id: None,
+            artist: meta.artist.clone(),
+            artist_unicode: meta.artist_unicode.clone(),
+            title: meta.title.clone(),
+            title_unicode: meta.title_unicode.clone(),
+            creator: meta.creator.clone(),
+            source: meta.source.clone(),
+            tags: (!meta.tags.is_empty()).then(|| meta.tags.iter().map(|t| t.to_lowercase()).collect()),
+            has_video: false,
+            has_storyboard: false,
+            is_explicit: false,
+            is_featured: false,
+            cover_url: None,
+            preview_url: None,
+            osu_file_url: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::models::extended::beatmap::BeatmapExtended;
 use crate::models::extended::complete::types::BeatmapCompleteExtended;
 use crate::models::extended::msd::MSDExtended;
+use crate::models::MsdPattern;
 use sqlx::{Error as SqlxError, PgPool};
 
 pub async fn find_by_beatmapset_id(
@@ -12,13 +13,18 @@ pub async fn find_by_beatmapset_id(
         SELECT b.id, b.osu_id, b.beatmapset_id, b.difficulty, b.difficulty_rating, 
                b.count_circles, b.count_sliders, b.count_spinners, b.max_combo,
                b.drain_time, b.total_time, b.bpm, b.cs, b.ar, b.od, b.hp, b.mode,
-               b.status, b.file_md5, b.file_path, b.created_at, b.updated_at,
-               m.id as msd_id, m.beatmap_id, m.overall, m.stream, m.jumpstream,
+               b.status, b.file_md5, b.file_path, b.key_count, b.avg_nps, b.peak_nps,
+               b.ln_count, b.ln_ratio, b.avg_ln_length,
+               b.created_at, b.updated_at,
+               m.id as "msd_id?", m.beatmap_id, m.overall, m.stream, m.jumpstream,
                m.handstream, m.stamina, m.jackspeed, m.chordjack, m.technical,
-               m.rate, m.main_pattern, m.created_at as msd_created_at,
+               m.rate, m.main_pattern,
+               m.primary_pattern as "primary_pattern: MsdPattern",
+               m.secondary_pattern as "secondary_pattern: MsdPattern",
+               m.pattern_dominance, m.calc_version, m.created_at as msd_created_at,
                m.updated_at as msd_updated_at
         FROM beatmap b
-        JOIN msd m ON b.id = m.beatmap_id
+        LEFT JOIN msd m ON b.id = m.beatmap_id
         WHERE b.beatmapset_id = $1
         "#,
         beatmapset_id
@@ -53,14 +59,22 @@ pub async fn find_by_beatmapset_id(
                 status: r.status.clone(),
                 file_md5: r.file_md5.clone(),
                 file_path: r.file_path.clone(),
+                key_count: r.key_count,
+                avg_nps: r.avg_nps.clone(),
+                peak_nps: r.peak_nps.clone(),
+                ln_count: r.ln_count,
+                ln_ratio: r.ln_ratio.clone(),
+                avg_ln_length: r.avg_ln_length,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
             }),
             msd: Vec::new(),
         });
 
+        // Les keymodes autres que 4K n'ont pas de MSD
+        let Some(msd_id) = r.msd_id else { continue };
         entry.msd.push(MSDExtended {
-            id: Some(r.msd_id),
+            id: Some(msd_id),
             beatmap_id: r.beatmap_id,
             overall: r.overall.clone(),
             stream: r.stream.clone(),
@@ -72,6 +86,10 @@ pub async fn find_by_beatmapset_id(
             technical: r.technical.clone(),
             rate: r.rate.clone(),
             main_pattern: r.main_pattern.clone(),
+            primary_pattern: r.primary_pattern,
+            secondary_pattern: r.secondary_pattern,
+            pattern_dominance: r.pattern_dominance.clone(),
+            calc_version: r.calc_version,
             created_at: r.msd_created_at,
             updated_at: r.msd_updated_at,
         });
@@ -1,12 +1,24 @@
 use crate::models::extended::msd::query::{
-    Insert, find_all_by_beatmap_id, find_by_beatmap_id, find_by_beatmap_id_and_rate, find_by_id,
+    find_all_by_beatmap_id, insert_many, find_by_beatmap_id, find_by_beatmap_id_and_rate, find_by_id,
+    replace_for_beatmap,
 };
 use crate::models::extended::msd::types::MSDExtended;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 impl MSDExtended {
-    pub async fn insert_into_db(&self, pool: &PgPool) -> Result<i32, sqlx::Error> {
-        self.insert(pool).await
+    pub async fn insert_many_into_db<'e, E>(executor: E, msds: &[Self]) -> Result<u64, sqlx::Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        insert_many(executor, msds).await
+    }
+
+    pub async fn replace_for_beatmap(
+        pool: &PgPool,
+        beatmap_id: i32,
+        msds: &[Self],
+    ) -> Result<u64, sqlx::Error> {
+        replace_for_beatmap(pool, beatmap_id, msds).await
     }
 
     pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<Self>, sqlx::Error> {
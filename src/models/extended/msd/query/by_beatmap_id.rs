@@ -1,4 +1,5 @@
 use crate::models::extended::msd::types::MSDExtended;
+use crate::models::MsdPattern;
 use sqlx::{Error as SqlxError, PgPool};
 
 pub async fn find_by_beatmap_id(
@@ -8,7 +9,12 @@ pub async fn find_by_beatmap_id(
     sqlx::query_as!(
         MSDExtended,
         r#"
-        SELECT * FROM msd WHERE beatmap_id = $1 ORDER BY created_at DESC LIMIT 1
+        SELECT id, beatmap_id, overall, stream, jumpstream, handstream, stamina, jackspeed,
+               chordjack, technical, rate, main_pattern,
+               primary_pattern as "primary_pattern: MsdPattern",
+               secondary_pattern as "secondary_pattern: MsdPattern",
+               pattern_dominance, calc_version, created_at, updated_at
+        FROM msd WHERE beatmap_id = $1 ORDER BY created_at DESC LIMIT 1
         "#,
         beatmap_id
     )
@@ -23,7 +29,14 @@ pub async fn find_by_beatmap_id_and_rate(
 ) -> Result<Option<MSDExtended>, SqlxError> {
     sqlx::query_as!(
         MSDExtended,
-        r#"SELECT * FROM msd WHERE beatmap_id = $1 AND rate = $2"#,
+        r#"
+        SELECT id, beatmap_id, overall, stream, jumpstream, handstream, stamina, jackspeed,
+               chordjack, technical, rate, main_pattern,
+               primary_pattern as "primary_pattern: MsdPattern",
+               secondary_pattern as "secondary_pattern: MsdPattern",
+               pattern_dominance, calc_version, created_at, updated_at
+        FROM msd WHERE beatmap_id = $1 AND rate = $2
+        "#,
         beatmap_id,
         crate::helpers::common::from_f64(rate)
     )
@@ -37,7 +50,14 @@ pub async fn find_all_by_beatmap_id(
 ) -> Result<Vec<MSDExtended>, SqlxError> {
     sqlx::query_as!(
         MSDExtended,
-        r#"SELECT * FROM msd WHERE beatmap_id = $1 ORDER BY created_at DESC"#,
+        r#"
+        SELECT id, beatmap_id, overall, stream, jumpstream, handstream, stamina, jackspeed,
+               chordjack, technical, rate, main_pattern,
+               primary_pattern as "primary_pattern: MsdPattern",
+               secondary_pattern as "secondary_pattern: MsdPattern",
+               pattern_dominance, calc_version, created_at, updated_at
+        FROM msd WHERE beatmap_id = $1 ORDER BY created_at DESC
+        "#,
         beatmap_id
     )
     .fetch_all(pool)
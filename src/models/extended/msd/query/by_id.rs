@@ -1,11 +1,17 @@
 use crate::models::extended::msd::types::MSDExtended;
+use crate::models::MsdPattern;
 use sqlx::{Error as SqlxError, PgPool};
 
 pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<MSDExtended>, SqlxError> {
     sqlx::query_as!(
         MSDExtended,
         r#"
-        SELECT * FROM msd WHERE id = $1
+        SELECT id, beatmap_id, overall, stream, jumpstream, handstream, stamina, jackspeed,
+               chordjack, technical, rate, main_pattern,
+               primary_pattern as "primary_pattern: MsdPattern",
+               secondary_pattern as "secondary_pattern: MsdPattern",
+               pattern_dominance, calc_version, created_at, updated_at
+        FROM msd WHERE id = $1
         "#,
         id
     )
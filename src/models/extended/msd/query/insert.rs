@@ -1,38 +1,56 @@
 use crate::models::extended::msd::types::MSDExtended;
-use async_trait::async_trait;
-use sqlx::{Error as SqlxError, PgPool};
+use sqlx::{Error as SqlxError, PgExecutor};
 
-#[async_trait]
-pub trait Insert {
-    async fn insert(&self, pool: &PgPool) -> Result<i32, SqlxError>;
-}
+/// Insère toutes les lignes MSD (une par rate) en une seule requête
+pub async fn insert_many<'e, E>(executor: E, msds: &[MSDExtended]) -> Result<u64, SqlxError>
+where
+    E: PgExecutor<'e>,
+{
+    if msds.is_empty() {
+        return Ok(0);
+    }
 
-#[async_trait]
-impl Insert for MSDExtended {
-    async fn insert(&self, pool: &PgPool) -> Result<i32, SqlxError> {
-        let row = sqlx::query!(
-            r#"
-            INSERT INTO msd (
-                beatmap_id, overall, stream, jumpstream, handstream,
-                stamina, jackspeed, chordjack, technical, rate, main_pattern
-            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
-            RETURNING id
-            "#,
-            self.beatmap_id,
-            self.overall.as_ref(),
-            self.stream.as_ref(),
-            self.jumpstream.as_ref(),
-            self.handstream.as_ref(),
-            self.stamina.as_ref(),
-            self.jackspeed.as_ref(),
-            self.chordjack.as_ref(),
-            self.technical.as_ref(),
-            self.rate.as_ref(),
-            self.main_pattern.as_deref()
-        )
-        .fetch_one(pool)
-        .await?;
+    const COLUMNS: usize = 15;
+    let placeholders: Vec<String> = (0..msds.len())
+        .map(|row| {
+            let params: Vec<String> = (1..=COLUMNS)
+                .map(|col| format!("${}", row * COLUMNS + col))
+                .collect();
+            format!("({})", params.join(","))
+        })
+        .collect();
 
-        Ok(row.id)
+    let query = format!(
+        r#"
+        INSERT INTO msd (
+            beatmap_id, overall, stream, jumpstream, handstream,
+            stamina, jackspeed, chordjack, technical, rate, main_pattern,
+            primary_pattern, secondary_pattern, pattern_dominance, calc_version
+        ) VALUES {}
+        "#,
+        placeholders.join(", ")
+    );
+
+    let mut q = sqlx::query(&query);
+    for msd in msds {
+        q = q
+            .bind(msd.beatmap_id)
+            .bind(msd.overall.as_ref())
+            .bind(msd.stream.as_ref())
+            .bind(msd.jumpstream.as_ref())
+            .bind(msd.handstream.as_ref())
+            .bind(msd.stamina.as_ref())
+            .bind(msd.jackspeed.as_ref())
+            .bind(msd.chordjack.as_ref())
+            .bind(msd.technical.as_ref())
+            .bind(msd.rate.as_ref())
+            .bind(msd.main_pattern.as_deref())
+            .bind(msd.primary_pattern)
+            .bind(msd.secondary_pattern)
+            .bind(msd.pattern_dominance.as_ref())
+            .bind(msd.calc_version);
     }
+
+    let result = q.execute(executor).await?;
+    Ok(result.rows_affected())
 }
@@ -2,8 +2,10 @@ pub mod by_beatmap_id;
 pub mod by_id;
 pub mod count_by_pattern;
 pub mod insert;
+pub mod replace;
 
 pub use by_beatmap_id::*;
 pub use by_id::*;
 pub use count_by_pattern::*;
-pub use insert::Insert;
+pub use insert::insert_many;
+pub use replace::*;
@@ -0,0 +1,21 @@
+use crate::models::extended::msd::query::insert_many;
+use crate::models::extended::msd::types::MSDExtended;
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Remplace toutes les lignes MSD d'une beatmap dans une transaction :
+/// les lecteurs voient les anciennes ou les nouvelles, jamais un mélange
+pub async fn replace_for_beatmap(
+    pool: &PgPool,
+    beatmap_id: i32,
+    msds: &[MSDExtended],
+) -> Result<u64, SqlxError> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!("DELETE FROM msd WHERE beatmap_id = $1", beatmap_id)
+        .execute(&mut *tx)
+        .await?;
+    let inserted = insert_many(&mut *tx, msds).await?;
+
+    tx.commit().await?;
+    Ok(inserted)
+}
@@ -1,5 +1,7 @@
-use crate::helpers::common::from_f32;
-use crate::helpers::msd::calculate_main_pattern;
+use crate::helpers::common::{from_f32, to_f32};
+use crate::helpers::msd::{calculate_main_pattern, calculate_pattern_profile};
+use crate::models::MsdPattern;
+use crate::services::msd_calculator::calc_version;
 use bigdecimal::BigDecimal;
 use chrono::NaiveDateTime;
 use minacalc_rs::Ssr;
@@ -19,12 +21,19 @@ pub struct MSDExtended {
     pub technical: Option<BigDecimal>,
     pub rate: Option<BigDecimal>,
     pub main_pattern: Option<String>,
+    pub primary_pattern: Option<MsdPattern>,
+    pub secondary_pattern: Option<MsdPattern>,
+    /// Rapport entre le premier et le deuxième skillset
+    pub pattern_dominance: Option<BigDecimal>,
+    /// Version de MinaCalc qui a produit la ligne (`None` : antérieure au suivi des versions)
+    pub calc_version: Option<i32>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
 }
 
 impl MSDExtended {
     pub fn from(ssr: Ssr, rate: f32) -> Self {
+        let profile = calculate_pattern_profile(&ssr);
         Self {
             id: None,
             beatmap_id: None,
@@ -38,8 +47,57 @@ impl MSDExtended {
             technical: Some(from_f32(ssr.technical)),
             rate: Some(from_f32(rate)),
             main_pattern: Some(calculate_main_pattern(&ssr)),
+            primary_pattern: Some(profile.primary),
+            secondary_pattern: Some(profile.secondary),
+            pattern_dominance: profile.dominance.map(from_f32),
+            calc_version: Some(calc_version()),
             created_at: None,
             updated_at: None,
         }
     }
 }
+
+impl MSDExtended {
+    fn ssr(&self) -> Ssr {
+        let value = |v: &Option<BigDecimal>| v.as_ref().map(to_f32).unwrap_or(0.0);
+        Ssr {
+            overall: value(&self.overall),
+            stream: value(&self.stream),
+            jumpstream: value(&self.jumpstream),
+            handstream: value(&self.handstream),
+            stamina: value(&self.stamina),
+            jackspeed: value(&self.jackspeed),
+            chordjack: value(&self.chordjack),
+            technical: value(&self.technical),
+        }
+    }
+
+    /// Interpolation linéaire entre deux rates stockés, `lower.rate <= rate <= upper.rate`
+    pub fn interpolate(lower: &Self, upper: &Self, rate: f32) -> Self {
+        let lower_rate = lower.rate.as_ref().map(to_f32).unwrap_or(rate);
+        let upper_rate = upper.rate.as_ref().map(to_f32).unwrap_or(rate);
+        let t = if upper_rate > lower_rate {
+            ((rate - lower_rate) / (upper_rate - lower_rate)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let (a, b) = (lower.ssr(), upper.ssr());
+        let lerp = |x: f32, y: f32| x + (y - x) * t;
+
+        let mut msd = Self::from(
+            Ssr {
+                overall: lerp(a.overall, b.overall),
+                stream: lerp(a.stream, b.stream),
+                jumpstream: lerp(a.jumpstream, b.jumpstream),
+                handstream: lerp(a.handstream, b.handstream),
+                stamina: lerp(a.stamina, b.stamina),
+                jackspeed: lerp(a.jackspeed, b.jackspeed),
+                chordjack: lerp(a.chordjack, b.chordjack),
+                technical: lerp(a.technical, b.technical),
+            },
+            rate,
+        );
+        msd.beatmap_id = lower.beatmap_id;
+        msd
+    }
+}
@@ -1,15 +1,34 @@
-use crate::models::failed_query::FailedQuery;
-use crate::models::failed_query::query::{delete_by_hash, exists_by_hash, find_by_id, insert};
+use crate::models::failed_query::{FailedQuery, FailureReason};
+use crate::models::failed_query::query::{
+    blocked_hashes, count, delete_by_hash, delete_older_than, find_by_hash, find_by_id, insert,
+    is_blocked_by_hash, list, requeue_due, retry_by_hash,
+};
+use chrono::NaiveDateTime;
 use sqlx::{Error as SqlxError, PgPool};
 
 impl FailedQuery {
-    pub async fn insert(pool: &PgPool, hash: &str) -> Result<i32, SqlxError> {
-        let result = insert(pool, hash).await?;
+    pub async fn insert(
+        pool: &PgPool,
+        hash: &str,
+        reason: FailureReason,
+        message: Option<&str>,
+    ) -> Result<i32, SqlxError> {
+        let result = insert(pool, hash, reason, message).await?;
         Ok(result)
     }
 
-    pub async fn exists_by_hash(pool: &PgPool, hash: &str) -> Result<bool, SqlxError> {
-        let result = exists_by_hash(pool, hash).await?;
+    pub async fn is_blocked_by_hash(pool: &PgPool, hash: &str) -> Result<bool, SqlxError> {
+        let result = is_blocked_by_hash(pool, hash).await?;
+        Ok(result)
+    }
+
+    pub async fn blocked_hashes(pool: &PgPool, hashes: &[String]) -> Result<Vec<String>, SqlxError> {
+        let result = blocked_hashes(pool, hashes).await?;
+        Ok(result)
+    }
+
+    pub async fn requeue_due(pool: &PgPool) -> Result<u64, SqlxError> {
+        let result = requeue_due(pool).await?;
         Ok(result)
     }
 
@@ -22,4 +41,41 @@ impl FailedQuery {
         let result = delete_by_hash(pool, hash).await?;
         Ok(result)
     }
+
+    pub async fn list(
+        pool: &PgPool,
+        reason: Option<FailureReason>,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<FailedQuery>, SqlxError> {
+        let result = list(pool, reason, from, to, limit, offset).await?;
+        Ok(result)
+    }
+
+    pub async fn count(
+        pool: &PgPool,
+        reason: Option<FailureReason>,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+    ) -> Result<i64, SqlxError> {
+        let result = count(pool, reason, from, to).await?;
+        Ok(result)
+    }
+
+    pub async fn retry_by_hash(pool: &PgPool, hash: &str) -> Result<bool, SqlxError> {
+        let result = retry_by_hash(pool, hash).await?;
+        Ok(result)
+    }
+
+    pub async fn delete_older_than(pool: &PgPool, date: NaiveDateTime) -> Result<u64, SqlxError> {
+        let result = delete_older_than(pool, date).await?;
+        Ok(result)
+    }
+
+    pub async fn find_by_hash(pool: &PgPool, hash: &str) -> Result<Option<FailedQuery>, SqlxError> {
+        let result = find_by_hash(pool, hash).await?;
+        Ok(result)
+    }
 }
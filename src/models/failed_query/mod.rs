@@ -2,4 +2,4 @@ pub mod r#impl;
 pub mod query;
 pub mod types;
 
-pub use types::FailedQuery;
+pub use types::{FailedQuery, FailureReason};
@@ -0,0 +1,19 @@
+use crate::models::failed_query::{FailedQuery, FailureReason};
+use sqlx::{Error as SqlxError, PgPool};
+
+pub async fn find_by_hash(pool: &PgPool, hash: &str) -> Result<Option<FailedQuery>, SqlxError> {
+    let row = sqlx::query_as!(
+        FailedQuery,
+        r#"
+        SELECT id, hash, reason as "reason: FailureReason", message, attempts, permanent,
+               next_retry_at, created_at, updated_at
+        FROM failed_query
+        WHERE hash = $1
+        "#,
+        hash
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row)
+}
@@ -1,11 +1,12 @@
-use crate::models::failed_query::FailedQuery;
+use crate::models::failed_query::{FailedQuery, FailureReason};
 use sqlx::{Error as SqlxError, PgPool};
 
 pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<FailedQuery>, SqlxError> {
     let row = sqlx::query_as!(
         FailedQuery,
         r#"
-        SELECT id, hash, created_at
+        SELECT id, hash, reason as "reason: FailureReason", message, attempts, permanent,
+               next_retry_at, created_at, updated_at
         FROM failed_query
         WHERE id = $1
         "#,
@@ -5,7 +5,7 @@ pub async fn delete_older_than(pool: &PgPool, date: NaiveDateTime) -> Result<u64
     let result = sqlx::query!(
         r#"
         DELETE FROM failed_query
-        WHERE created_at < $1   
+        WHERE updated_at < $1
         "#,
         date
     )
@@ -1,14 +1,44 @@
-use crate::models::failed_query::types::FailedQuery;
+use crate::models::failed_query::types::{
+    FailureReason, MAX_RETRY_ATTEMPTS, RETRY_BASE_SECS, RETRY_MAX_SECS,
+};
 use sqlx::{Error as SqlxError, PgPool};
 
-pub async fn insert(pool: &PgPool, hash: &str) -> Result<i32, SqlxError> {
+/// Enregistre un échec, ou incrémente le compteur si le hash a déjà échoué.
+///
+/// Les erreurs transitoires sont replanifiées avec un backoff exponentiel
+/// jusqu'à `MAX_RETRY_ATTEMPTS`, les autres sont immédiatement permanentes.
+pub async fn insert(
+    pool: &PgPool,
+    hash: &str,
+    reason: FailureReason,
+    message: Option<&str>,
+) -> Result<i32, SqlxError> {
     let result = sqlx::query!(
         r#"
-        INSERT INTO failed_query (hash)
-        VALUES ($1)
+        INSERT INTO failed_query AS f (hash, reason, message, attempts, permanent, next_retry_at)
+        VALUES (
+            $1, $2, $3, 1, NOT $4,
+            CASE WHEN $4 THEN now() + make_interval(secs => $5) END
+        )
+        ON CONFLICT (hash) DO UPDATE SET
+            reason = EXCLUDED.reason,
+            message = EXCLUDED.message,
+            attempts = f.attempts + 1,
+            permanent = NOT $4 OR f.attempts + 1 >= $7,
+            next_retry_at = CASE
+                WHEN $4 AND f.attempts + 1 < $7
+                THEN now() + make_interval(secs => LEAST($5 * power(2, f.attempts), $6))
+            END,
+            updated_at = now()
         RETURNING id
         "#,
-        hash
+        hash,
+        reason as FailureReason,
+        message,
+        reason.is_transient(),
+        RETRY_BASE_SECS,
+        RETRY_MAX_SECS,
+        MAX_RETRY_ATTEMPTS
     )
     .fetch_one(pool)
     .await?;
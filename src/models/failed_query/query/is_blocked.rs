@@ -0,0 +1,34 @@
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Un hash est bloqué si son échec est permanent ou si sa prochaine tentative n'est pas encore due
+pub async fn is_blocked_by_hash(pool: &PgPool, hash: &str) -> Result<bool, SqlxError> {
+    let result = sqlx::query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM failed_query
+            WHERE hash = $1
+              AND (permanent OR next_retry_at IS NULL OR next_retry_at > now())
+        ) as exists
+        "#,
+        hash
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(result.exists.unwrap_or(false))
+}
+
+/// Retourne, parmi les hashes donnés, ceux actuellement bloqués
+pub async fn blocked_hashes(pool: &PgPool, hashes: &[String]) -> Result<Vec<String>, SqlxError> {
+    let rows = sqlx::query_scalar!(
+        r#"
+        SELECT hash
+        FROM failed_query
+        WHERE hash = ANY($1)
+          AND (permanent OR next_retry_at IS NULL OR next_retry_at > now())
+        "#,
+        hashes
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
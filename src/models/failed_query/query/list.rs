@@ -0,0 +1,60 @@
+use crate::models::failed_query::{FailedQuery, FailureReason};
+use chrono::NaiveDateTime;
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Liste les échecs, du plus récent au plus ancien, filtrés par raison et date du dernier échec
+pub async fn list(
+    pool: &PgPool,
+    reason: Option<FailureReason>,
+    from: Option<NaiveDateTime>,
+    to: Option<NaiveDateTime>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<FailedQuery>, SqlxError> {
+    let rows = sqlx::query_as!(
+        FailedQuery,
+        r#"
+        SELECT id, hash, reason as "reason: FailureReason", message, attempts, permanent,
+               next_retry_at, created_at, updated_at
+        FROM failed_query
+        WHERE ($1::varchar IS NULL OR reason = $1)
+          AND ($2::timestamp IS NULL OR updated_at >= $2)
+          AND ($3::timestamp IS NULL OR updated_at < $3)
+        ORDER BY updated_at DESC, id DESC
+        LIMIT $4 OFFSET $5
+        "#,
+        reason as Option<FailureReason>,
+        from,
+        to,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+pub async fn count(
+    pool: &PgPool,
+    reason: Option<FailureReason>,
+    from: Option<NaiveDateTime>,
+    to: Option<NaiveDateTime>,
+) -> Result<i64, SqlxError> {
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM failed_query
+        WHERE ($1::varchar IS NULL OR reason = $1)
+          AND ($2::timestamp IS NULL OR updated_at >= $2)
+          AND ($3::timestamp IS NULL OR updated_at < $3)
+        "#,
+        reason as Option<FailureReason>,
+        from,
+        to
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result.count)
+}
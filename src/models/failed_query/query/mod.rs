@@ -1,11 +1,19 @@
+pub mod by_hash;
 pub mod by_id;
 pub mod delete_by_hash;
 pub mod delete_older_than;
-pub mod exists_by_hash;
 pub mod insert;
+pub mod is_blocked;
+pub mod list;
+pub mod requeue_due;
+pub mod retry;
 
+pub use by_hash::find_by_hash;
 pub use by_id::find_by_id;
 pub use delete_by_hash::delete_by_hash;
 pub use delete_older_than::delete_older_than;
-pub use exists_by_hash::exists_by_hash;
 pub use insert::insert;
+pub use is_blocked::{blocked_hashes, is_blocked_by_hash};
+pub use list::{count, list};
+pub use requeue_due::requeue_due;
+pub use retry::retry_by_hash;
@@ -0,0 +1,21 @@
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Remet dans pending_beatmap les échecs transitoires dont la prochaine tentative est due.
+///
+/// La ligne failed_query est conservée pour garder le compteur de tentatives,
+/// elle est supprimée quand le traitement finit par réussir.
+pub async fn requeue_due(pool: &PgPool) -> Result<u64, SqlxError> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO pending_beatmap (hash)
+        SELECT hash
+        FROM failed_query
+        WHERE NOT permanent AND next_retry_at <= now()
+        ON CONFLICT (hash) DO NOTHING
+        "#
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
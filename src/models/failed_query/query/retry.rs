@@ -0,0 +1,25 @@
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Déplace un échec vers pending_beatmap en une seule requête.
+///
+/// Retourne `false` si le hash n'était pas en échec. S'il est déjà en file,
+/// l'échec est quand même supprimé.
+pub async fn retry_by_hash(pool: &PgPool, hash: &str) -> Result<bool, SqlxError> {
+    let result = sqlx::query!(
+        r#"
+        WITH moved AS (
+            DELETE FROM failed_query WHERE hash = $1 RETURNING hash
+        ), queued AS (
+            INSERT INTO pending_beatmap (hash)
+            SELECT hash FROM moved
+            ON CONFLICT (hash) DO NOTHING
+        )
+        SELECT COUNT(*) as "count!" FROM moved
+        "#,
+        hash
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result.count > 0)
+}
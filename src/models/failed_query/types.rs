@@ -1,9 +1,42 @@
 use chrono::NaiveDateTime;
-use sqlx::{Error as SqlxError, PgPool};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+/// Délai avant la première nouvelle tentative, doublé à chaque échec
+pub const RETRY_BASE_SECS: f64 = 300.0;
+/// Délai maximum entre deux tentatives (24h)
+pub const RETRY_MAX_SECS: f64 = 86400.0;
+/// Au-delà, même une erreur transitoire devient permanente
+pub const MAX_RETRY_ATTEMPTS: i32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum FailureReason {
+    NotFound,
+    NotAllowedMode,
+    ParseError,
+    Network,
+    CalcError,
+    /// Bail expiré trop de fois : le traitement fait sûrement tomber le process
+    LeaseExpired,
+}
+
+impl FailureReason {
+    /// Seules les erreurs transitoires sont retentées automatiquement
+    pub fn is_transient(&self) -> bool {
+        matches!(self, FailureReason::Network)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct FailedQuery {
     pub id: i32,
     pub hash: String,
+    pub reason: FailureReason,
+    pub message: Option<String>,
+    pub attempts: i32,
+    pub permanent: bool,
+    pub next_retry_at: Option<NaiveDateTime>,
     pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
 }
@@ -2,16 +2,21 @@
 // Example:
 // pub mod user;
 // pub mod product;
-use serde::Deserialize;
+use crate::helpers::search::SearchQuery;
+use serde::{Deserialize, Deserializer, Serialize};
+pub mod beatmap_density;
+pub mod cursor;
 pub mod extended;
 pub mod failed_query;
 pub mod help;
+pub mod pending_batch;
 pub mod pending_beatmap;
 pub mod short;
 
 
-#[derive(Debug, Clone, Deserialize, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
 #[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
 pub enum MsdPattern {
     Stream,
     Jumpstream,
@@ -23,6 +28,17 @@ pub enum MsdPattern {
 }
 
 impl MsdPattern {
+    /// Tous les skillsets, dans l'ordre de départage en cas d'égalité
+    pub const ALL: [MsdPattern; 7] = [
+        MsdPattern::Stream,
+        MsdPattern::Jumpstream,
+        MsdPattern::Handstream,
+        MsdPattern::Stamina,
+        MsdPattern::Jackspeed,
+        MsdPattern::Chordjack,
+        MsdPattern::Technical,
+    ];
+
     /// Retourne le nom de colonne sécurisé pour ce pattern
     pub fn as_column_name(&self) -> &'static str {
         match self {
@@ -35,22 +51,271 @@ impl MsdPattern {
             MsdPattern::Technical => "technical",
         }
     }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        MsdPattern::ALL
+            .into_iter()
+            .find(|pattern| pattern.as_column_name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Désérialise une liste de skillsets séparés par des virgules (`chordjack,jackspeed`)
+fn deserialize_pattern_list<'de, D>(deserializer: D) -> Result<Vec<MsdPattern>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            MsdPattern::from_name(name)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown pattern '{}'", name)))
+        })
+        .collect()
+}
+
+/// Statut d'une beatmap, tel que stocké dans `beatmap.status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BeatmapStatus {
+    Pending,
+    Ranked,
+    Approved,
+    Qualified,
+    Loved,
+    Graveyard,
+    Wip,
+    /// Importée d'un .osz, inconnue de l'API osu!
+    Local,
+}
+
+impl BeatmapStatus {
+    pub const ALL: [BeatmapStatus; 8] = [
+        BeatmapStatus::Pending,
+        BeatmapStatus::Ranked,
+        BeatmapStatus::Approved,
+        BeatmapStatus::Qualified,
+        BeatmapStatus::Loved,
+        BeatmapStatus::Graveyard,
+        BeatmapStatus::Wip,
+        BeatmapStatus::Local,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BeatmapStatus::Pending => "pending",
+            BeatmapStatus::Ranked => "ranked",
+            BeatmapStatus::Approved => "approved",
+            BeatmapStatus::Qualified => "qualified",
+            BeatmapStatus::Loved => "loved",
+            BeatmapStatus::Graveyard => "graveyard",
+            BeatmapStatus::Wip => "wip",
+            BeatmapStatus::Local => "local",
+        }
+    }
+}
+
+/// Clés de tri de la liste filtrée
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    Overall,
+    Stream,
+    Jumpstream,
+    Handstream,
+    Stamina,
+    Jackspeed,
+    Chordjack,
+    Technical,
+    Bpm,
+    TotalTime,
+    CreatedAt,
+    DifficultyRating,
+    Title,
+    Artist,
+    /// Pertinence de la recherche plein texte, par défaut quand le terme a des mots libres
+    Relevance,
+}
+
+impl SortBy {
+    /// Retourne l'expression SQL sécurisée pour cette clé de tri
+    pub fn as_order_expr(&self) -> &'static str {
+        match self {
+            SortBy::Overall => "m.overall",
+            SortBy::Stream => "m.stream",
+            SortBy::Jumpstream => "m.jumpstream",
+            SortBy::Handstream => "m.handstream",
+            SortBy::Stamina => "m.stamina",
+            SortBy::Jackspeed => "m.jackspeed",
+            SortBy::Chordjack => "m.chordjack",
+            SortBy::Technical => "m.technical",
+            SortBy::Bpm => "b.bpm",
+            SortBy::TotalTime => "b.total_time",
+            SortBy::CreatedAt => "bs.created_at",
+            SortBy::DifficultyRating => "b.difficulty_rating",
+            SortBy::Title => "bs.title",
+            SortBy::Artist => "bs.artist",
+            // Les mots libres sont toujours le premier paramètre (voir build_where_conditions)
+            SortBy::Relevance => "ts_rank(bs.search_vector, to_tsquery('simple', $1))",
+        }
+    }
+
+    /// Type SQL de la clé, pour relire la valeur stockée dans un curseur
+    pub fn sql_type(&self) -> &'static str {
+        match self {
+            SortBy::TotalTime => "integer",
+            SortBy::CreatedAt => "timestamp",
+            SortBy::Title | SortBy::Artist => "text",
+            SortBy::Relevance => "real",
+            _ => "numeric",
+        }
+    }
+
+    /// Ordre alphabétique pour le texte, valeurs les plus hautes (ou récentes) d'abord sinon
+    pub fn default_dir(&self) -> SortDir {
+        match self {
+            SortBy::Title | SortBy::Artist => SortDir::Asc,
+            _ => SortDir::Desc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDir {
+    Asc,
+    Desc,
+}
+
+impl SortDir {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortDir::Asc => "ASC",
+            SortDir::Desc => "DESC",
+        }
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct Filters {
+    /// Mots libres et champs qualifiés (`creator:Evening artist:"Camellia"`)
     pub search_term: Option<String>,
     pub overall_min: Option<f64>,
     pub overall_max: Option<f64>,
+    /// Pattern parmi les deux skillsets dominants
     pub selected_pattern: Option<MsdPattern>,
+    /// Deuxième skillset dominant
+    pub secondary_pattern: Option<MsdPattern>,
+    /// Skillsets exclus comme skillset dominant
+    #[serde(default, deserialize_with = "deserialize_pattern_list")]
+    pub excluded_patterns: Vec<MsdPattern>,
+    /// Rapport entre le premier et le deuxième skillset (1.0 : à égalité)
+    pub pattern_dominance_min: Option<f64>,
+    pub pattern_dominance_max: Option<f64>,
     pub pattern_min: Option<f64>,
     pub pattern_max: Option<f64>,
+    /// Bornes par skillset, combinables entre elles
+    pub stream_min: Option<f64>,
+    pub stream_max: Option<f64>,
+    pub jumpstream_min: Option<f64>,
+    pub jumpstream_max: Option<f64>,
+    pub handstream_min: Option<f64>,
+    pub handstream_max: Option<f64>,
+    pub stamina_min: Option<f64>,
+    pub stamina_max: Option<f64>,
+    pub jackspeed_min: Option<f64>,
+    pub jackspeed_max: Option<f64>,
+    pub chordjack_min: Option<f64>,
+    pub chordjack_max: Option<f64>,
+    pub technical_min: Option<f64>,
+    pub technical_max: Option<f64>,
     pub bpm_min: Option<f64>,
     pub bpm_max: Option<f64>,
     pub total_time_min: Option<i32>,
     pub total_time_max: Option<i32>,
+    pub od_min: Option<f64>,
+    pub od_max: Option<f64>,
+    pub hp_min: Option<f64>,
+    pub hp_max: Option<f64>,
+    pub key_count_min: Option<i32>,
+    pub key_count_max: Option<i32>,
+    pub status: Option<BeatmapStatus>,
+    /// Mode osu! (0 osu, 1 taiko, 2 fruits, 3 mania)
+    pub mode: Option<i32>,
+    /// Part de LN, entre 0 et 1
+    pub ln_ratio_min: Option<f64>,
+    pub ln_ratio_max: Option<f64>,
+    /// Rate exact des MSD filtrées (1.0 si aucun filtre de rate)
+    pub rate: Option<f64>,
+    /// Plage de rates : une beatmap passe si l'un de ses rates dans la plage passe
+    pub rate_min: Option<f64>,
+    pub rate_max: Option<f64>,
+    /// Tous les rates stockés (0.7 à 2.0)
+    pub any_rate: Option<bool>,
+    pub sort_by: Option<SortBy>,
+    /// Sens du tri, selon la clé par défaut
+    pub sort_dir: Option<SortDir>,
     pub page: Option<usize>,
     pub per_page: Option<usize>,
+    /// Requête avancée (`stream>=24 bpm<200 status=ranked`), ajoutée aux autres paramètres
+    pub q: Option<String>,
+    /// Suite d'une liste paginée par curseur (`next_cursor` de la page précédente) ; remplace `page`
+    #[serde(default, deserialize_with = "cursor::deserialize")]
+    pub cursor: Option<cursor::Cursor>,
+}
+
+impl Filters {
+    /// Bornes min/max de chaque skillset
+    pub fn skillset_ranges(&self) -> [(MsdPattern, Option<f64>, Option<f64>); 7] {
+        [
+            (MsdPattern::Stream, self.stream_min, self.stream_max),
+            (MsdPattern::Jumpstream, self.jumpstream_min, self.jumpstream_max),
+            (MsdPattern::Handstream, self.handstream_min, self.handstream_max),
+            (MsdPattern::Stamina, self.stamina_min, self.stamina_max),
+            (MsdPattern::Jackspeed, self.jackspeed_min, self.jackspeed_max),
+            (MsdPattern::Chordjack, self.chordjack_min, self.chordjack_max),
+            (MsdPattern::Technical, self.technical_min, self.technical_max),
+        ]
+    }
+
+    /// Bornes modifiables d'un skillset
+    pub fn skillset_range_mut(&mut self, pattern: MsdPattern) -> (&mut Option<f64>, &mut Option<f64>) {
+        match pattern {
+            MsdPattern::Stream => (&mut self.stream_min, &mut self.stream_max),
+            MsdPattern::Jumpstream => (&mut self.jumpstream_min, &mut self.jumpstream_max),
+            MsdPattern::Handstream => (&mut self.handstream_min, &mut self.handstream_max),
+            MsdPattern::Stamina => (&mut self.stamina_min, &mut self.stamina_max),
+            MsdPattern::Jackspeed => (&mut self.jackspeed_min, &mut self.jackspeed_max),
+            MsdPattern::Chordjack => (&mut self.chordjack_min, &mut self.chordjack_max),
+            MsdPattern::Technical => (&mut self.technical_min, &mut self.technical_max),
+        }
+    }
+
+    fn has_search_text(&self) -> bool {
+        self.search_term
+            .as_deref()
+            .is_some_and(|term| SearchQuery::parse(term).text.is_some())
+    }
+
+    /// Clé de tri effective : la pertinence n'a de sens qu'avec des mots libres, et s'applique par défaut
+    pub fn resolved_sort_by(&self) -> Option<SortBy> {
+        match self.sort_by {
+            Some(SortBy::Relevance) if !self.has_search_text() => None,
+            Some(sort_by) => Some(sort_by),
+            None if self.has_search_text() => Some(SortBy::Relevance),
+            None => None,
+        }
+    }
+
+    /// Sens du tri effectif : celui demandé, sinon celui de la clé ; croissant sans tri
+    pub fn resolved_sort_dir(&self) -> SortDir {
+        match self.resolved_sort_by() {
+            Some(sort_by) => self.sort_dir.unwrap_or_else(|| sort_by.default_dir()),
+            None => SortDir::Asc,
+        }
+    }
 }
 
 
@@ -0,0 +1,22 @@
+use crate::models::pending_batch::query::*;
+use crate::models::pending_batch::types::{PendingBatch, PendingBatchItem, SubmitStatus};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+impl PendingBatch {
+    pub async fn insert(pool: &PgPool, items: &[(String, SubmitStatus)]) -> Result<Uuid, sqlx::Error> {
+        insert(pool, items).await
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        find_by_id(pool, id).await
+    }
+
+    pub async fn items(pool: &PgPool, id: Uuid) -> Result<Vec<PendingBatchItem>, sqlx::Error> {
+        items_by_batch_id(pool, id).await
+    }
+
+    pub async fn delete_finished_before(pool: &PgPool, retention_days: u32) -> Result<u64, sqlx::Error> {
+        delete_finished_before(pool, retention_days).await
+    }
+}
@@ -0,0 +1,5 @@
+pub mod r#impl;
+pub mod query;
+pub mod types;
+
+pub use types::*;
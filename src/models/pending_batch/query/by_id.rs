@@ -0,0 +1,52 @@
+use crate::models::failed_query::FailureReason;
+use crate::models::pending_batch::types::{PendingBatch, PendingBatchItem, SubmitStatus};
+use sqlx::{Error as SqlxError, PgPool};
+use uuid::Uuid;
+
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<PendingBatch>, SqlxError> {
+    let row = sqlx::query_as!(
+        PendingBatch,
+        r#"SELECT id, created_at FROM pending_batch WHERE id = $1"#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row)
+}
+
+/// Checksums du batch, dans l'ordre de soumission, avec leur état actuel
+pub async fn items_by_batch_id(
+    pool: &PgPool,
+    batch_id: Uuid,
+) -> Result<Vec<PendingBatchItem>, SqlxError> {
+    let rows = sqlx::query_as!(
+        PendingBatchItem,
+        r#"
+        WITH ranked AS (
+            SELECT
+                hash,
+                lease_expires_at,
+                ROW_NUMBER() OVER (ORDER BY priority DESC, created_at ASC, id ASC) as position
+            FROM pending_beatmap
+        )
+        SELECT
+            i.hash,
+            i.submit_status as "submit_status: SubmitStatus",
+            EXISTS(SELECT 1 FROM beatmap b WHERE b.file_md5 = i.hash) as "processed!",
+            r.position as "position?",
+            r.lease_expires_at > now() as "processing?",
+            f.reason as "failure_reason?: FailureReason"
+        FROM pending_batch_item i
+        LEFT JOIN ranked r ON r.hash = i.hash
+        LEFT JOIN failed_query f ON f.hash = i.hash
+        WHERE i.batch_id = $1
+        ORDER BY i.item_order
+        "#,
+        batch_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
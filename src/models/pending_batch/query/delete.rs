@@ -0,0 +1,23 @@
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Supprime les batches plus vieux que `retention_days` dont plus aucun checksum n'est en file
+/// (leurs items suivent par `on delete cascade`)
+pub async fn delete_finished_before(pool: &PgPool, retention_days: u32) -> Result<u64, SqlxError> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM pending_batch b
+        WHERE b.created_at < now() - make_interval(days => $1)
+          AND NOT EXISTS (
+              SELECT 1
+              FROM pending_batch_item i
+              JOIN pending_beatmap p ON p.hash = i.hash
+              WHERE i.batch_id = b.id
+          )
+        "#,
+    )
+    .bind(retention_days as i32)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
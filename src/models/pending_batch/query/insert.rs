@@ -0,0 +1,36 @@
+use crate::models::pending_batch::types::SubmitStatus;
+use sqlx::{Error as SqlxError, PgPool};
+use uuid::Uuid;
+
+/// Crée un batch et ses checksums (sans doublons) dans une transaction
+pub async fn insert(pool: &PgPool, items: &[(String, SubmitStatus)]) -> Result<Uuid, SqlxError> {
+    let id = Uuid::new_v4();
+    let hashes: Vec<String> = items.iter().map(|(hash, _)| hash.clone()).collect();
+    let statuses: Vec<String> = items
+        .iter()
+        .map(|(_, status)| status.as_str().to_string())
+        .collect();
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!("INSERT INTO pending_batch (id) VALUES ($1)", id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO pending_batch_item (batch_id, hash, item_order, submit_status)
+        SELECT $1, item.hash, item.ord::integer, item.status
+        FROM UNNEST($2::text[], $3::varchar[]) WITH ORDINALITY AS item(hash, status, ord)
+        ON CONFLICT (batch_id, hash) DO NOTHING
+        "#,
+        id,
+        &hashes,
+        &statuses
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(id)
+}
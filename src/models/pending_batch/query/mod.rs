@@ -0,0 +1,7 @@
+pub mod by_id;
+pub mod delete;
+pub mod insert;
+
+pub use by_id::*;
+pub use delete::*;
+pub use insert::*;
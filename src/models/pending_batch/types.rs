@@ -0,0 +1,68 @@
+use crate::models::failed_query::FailureReason;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Statut d'un checksum au moment de la soumission du batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum SubmitStatus {
+    Queued,
+    AlreadyProcessed,
+    PreviouslyFailed,
+    Duplicate,
+}
+
+impl SubmitStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmitStatus::Queued => "queued",
+            SubmitStatus::AlreadyProcessed => "already_processed",
+            SubmitStatus::PreviouslyFailed => "previously_failed",
+            SubmitStatus::Duplicate => "duplicate",
+        }
+    }
+}
+
+/// Statut actuel d'un checksum du batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchItemStatus {
+    Queued,
+    Processing,
+    Processed,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingBatch {
+    pub id: Uuid,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// Un checksum du batch, joint à son état dans pending_beatmap, beatmap et failed_query
+#[derive(Debug, Clone)]
+pub struct PendingBatchItem {
+    pub hash: String,
+    pub submit_status: SubmitStatus,
+    pub processed: bool,
+    pub position: Option<i64>,
+    pub processing: Option<bool>,
+    pub failure_reason: Option<FailureReason>,
+}
+
+impl PendingBatchItem {
+    pub fn status(&self) -> BatchItemStatus {
+        if self.processed {
+            BatchItemStatus::Processed
+        } else if self.processing.unwrap_or(false) {
+            BatchItemStatus::Processing
+        } else if self.position.is_some() {
+            BatchItemStatus::Queued
+        } else {
+            // Ni en base, ni en file : rejeté ou en attente d'une nouvelle tentative
+            BatchItemStatus::Failed
+        }
+    }
+}
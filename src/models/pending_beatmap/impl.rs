@@ -1,11 +1,16 @@
 use crate::models::pending_beatmap::query::*;
-use crate::models::pending_beatmap::types::PendingBeatmap;
+use crate::models::pending_beatmap::types::{PendingBeatmap, PendingPriority};
 use sqlx::PgPool;
 
 impl PendingBeatmap {
-    pub async fn insert(pool: &PgPool, hash: &str, osu_id: Option<i32>) -> Result<i32, sqlx::Error> {
+    pub async fn insert(
+        pool: &PgPool,
+        hash: &str,
+        osu_id: Option<i32>,
+        priority: PendingPriority,
+    ) -> Result<i32, sqlx::Error> {
         println!("DEBUG: Inserting beatmap: {:?}, {:?}", hash, osu_id);
-        insert(pool, hash, osu_id).await
+        insert(pool, hash, osu_id, priority).await
     }
 
     pub async fn delete_by_id(pool: &PgPool, id: i32) -> Result<u64, sqlx::Error> {
@@ -20,8 +25,16 @@ impl PendingBeatmap {
         count(pool).await
     }
 
-    pub async fn oldest(pool: &PgPool) -> Result<Option<Self>, sqlx::Error> {
-        oldest(pool).await
+    pub async fn claim_next(pool: &PgPool, lease_secs: u64) -> Result<Option<Self>, sqlx::Error> {
+        claim_next(pool, lease_secs).await
+    }
+
+    pub async fn renew_lease(pool: &PgPool, id: i32, lease_secs: u64) -> Result<u64, sqlx::Error> {
+        renew_lease(pool, id, lease_secs).await
+    }
+
+    pub async fn release_lease(pool: &PgPool, id: i32, delay_secs: u64) -> Result<u64, sqlx::Error> {
+        release_lease(pool, id, delay_secs).await
     }
 
     pub async fn bulk_insert(pool: &PgPool, hashes: &[String]) -> Result<usize, sqlx::Error> {
@@ -31,4 +44,16 @@ impl PendingBeatmap {
     pub async fn position_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<i64>, sqlx::Error> {
         position_by_osu_id(pool, osu_id).await
     }
+
+    pub async fn position_by_hash(pool: &PgPool, hash: &str) -> Result<Option<i64>, sqlx::Error> {
+        position_by_hash(pool, hash).await
+    }
+
+    pub async fn is_claimed_by_hash(pool: &PgPool, hash: &str) -> Result<bool, sqlx::Error> {
+        is_claimed_by_hash(pool, hash).await
+    }
+
+    pub async fn hash_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<String>, sqlx::Error> {
+        hash_by_osu_id(pool, osu_id).await
+    }
 }
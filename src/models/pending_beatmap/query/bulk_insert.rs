@@ -1,3 +1,4 @@
+use crate::models::pending_beatmap::types::PendingPriority;
 use sqlx::{Error as SqlxError, PgPool};
 
 pub async fn bulk_insert(pool: &PgPool, hashes: &[String]) -> Result<usize, SqlxError> {
@@ -5,10 +6,14 @@ pub async fn bulk_insert(pool: &PgPool, hashes: &[String]) -> Result<usize, Sqlx
         return Ok(0);
     }
 
-    let placeholders: Vec<String> = (1..=hashes.len()).map(|i| format!("(${})", i)).collect();
+    // Le dernier paramètre est la priorité, commune à tout le lot
+    let priority_param = hashes.len() + 1;
+    let placeholders: Vec<String> = (1..=hashes.len())
+        .map(|i| format!("(${}, ${})", i, priority_param))
+        .collect();
 
     let query = format!(
-        "INSERT INTO pending_beatmap (hash) VALUES {} ON CONFLICT (hash) DO NOTHING",
+        "INSERT INTO pending_beatmap (hash, priority) VALUES {} ON CONFLICT (hash) DO NOTHING",
         placeholders.join(", ")
     );
 
@@ -16,6 +21,7 @@ pub async fn bulk_insert(pool: &PgPool, hashes: &[String]) -> Result<usize, Sqlx
     for hash in hashes {
         q = q.bind(hash);
     }
+    q = q.bind(PendingPriority::Bulk.value());
 
     // Retourne le nombre de lignes affectées
     let result = q.execute(pool).await?;
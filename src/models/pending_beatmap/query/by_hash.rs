@@ -0,0 +1,33 @@
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Vrai si un worker a réclamé ce hash et que son bail court toujours
+pub async fn is_claimed_by_hash(pool: &PgPool, hash: &str) -> Result<bool, SqlxError> {
+    let result = sqlx::query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM pending_beatmap WHERE hash = $1 AND lease_expires_at > now()
+        ) as exists
+        "#,
+        hash
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(result.exists.unwrap_or(false))
+}
+
+/// Retrouve le checksum d'un id osu!, qu'il soit encore en file ou déjà traité
+pub async fn hash_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<String>, SqlxError> {
+    let hash = sqlx::query_scalar!(
+        r#"
+        SELECT hash as "hash!" FROM pending_beatmap WHERE osu_id = $1
+        UNION ALL
+        SELECT file_md5 FROM beatmap WHERE osu_id = $1
+        LIMIT 1
+        "#,
+        osu_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(hash)
+}
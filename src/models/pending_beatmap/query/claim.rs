@@ -0,0 +1,59 @@
+use crate::models::pending_beatmap::types::PendingBeatmap;
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Réclame atomiquement le pending_beatmap libre le plus prioritaire (puis le plus ancien) et lui pose un bail.
+///
+/// `FOR UPDATE SKIP LOCKED` permet à plusieurs workers de réclamer en parallèle
+/// sans jamais récupérer la même ligne. La ligne reste en base jusqu'à la fin du
+/// traitement : si le process meurt, elle redevient réclamable à l'expiration du bail.
+pub async fn claim_next(pool: &PgPool, lease_secs: u64) -> Result<Option<PendingBeatmap>, SqlxError> {
+    let row = sqlx::query_as!(
+        PendingBeatmap,
+        r#"
+        UPDATE pending_beatmap
+        SET claimed_at = now(),
+            lease_expires_at = now() + make_interval(secs => $1),
+            attempts = attempts + 1
+        WHERE id = (
+            SELECT id
+            FROM pending_beatmap
+            WHERE lease_expires_at IS NULL OR lease_expires_at < now()
+            ORDER BY priority DESC, created_at ASC, id ASC
+            FOR UPDATE SKIP LOCKED
+            LIMIT 1
+        )
+        RETURNING id, hash, osu_id, created_at, attempts
+        "#,
+        lease_secs as f64
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row)
+}
+
+/// Prolonge le bail d'une ligne réclamée, tant que son traitement tourne
+pub async fn renew_lease(pool: &PgPool, id: i32, lease_secs: u64) -> Result<u64, SqlxError> {
+    let result = sqlx::query(
+        r#"UPDATE pending_beatmap SET lease_expires_at = now() + make_interval(secs => $2) WHERE id = $1"#,
+    )
+    .bind(id)
+    .bind(lease_secs as f64)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Rend une ligne réclamable après `delay_secs` sans attendre l'expiration du bail
+pub async fn release_lease(pool: &PgPool, id: i32, delay_secs: u64) -> Result<u64, SqlxError> {
+    let result = sqlx::query(
+        r#"UPDATE pending_beatmap SET lease_expires_at = now() + make_interval(secs => $2) WHERE id = $1"#,
+    )
+    .bind(id)
+    .bind(delay_secs as f64)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
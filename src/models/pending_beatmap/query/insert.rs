@@ -1,16 +1,26 @@
+use crate::models::pending_beatmap::types::PendingPriority;
 use sqlx::{Error as SqlxError, PgPool, Row};
 
-pub async fn insert(pool: &PgPool, hash: &str, osu_id: Option<i32>) -> Result<i32, SqlxError> {
+/// Ajoute un hash à la file. S'il y est déjà, il garde la plus haute des deux priorités.
+pub async fn insert(
+    pool: &PgPool,
+    hash: &str,
+    osu_id: Option<i32>,
+    priority: PendingPriority,
+) -> Result<i32, SqlxError> {
     let row = sqlx::query(
         r#"
-        INSERT INTO pending_beatmap (hash, osu_id)
-        VALUES ($1, $2)
-        ON CONFLICT (hash) DO NOTHING
+        INSERT INTO pending_beatmap (hash, osu_id, priority)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (hash) DO UPDATE SET
+            osu_id = COALESCE(pending_beatmap.osu_id, EXCLUDED.osu_id),
+            priority = GREATEST(pending_beatmap.priority, EXCLUDED.priority)
         RETURNING id
         "#,
     )
     .bind(hash)
     .bind(osu_id)
+    .bind(priority.value())
     .fetch_optional(pool)
     .await?;
 
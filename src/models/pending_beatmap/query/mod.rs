@@ -1,12 +1,16 @@
 pub mod bulk_insert;
+pub mod by_hash;
+pub mod claim;
 pub mod count;
 pub mod delete;
 pub mod insert;
-pub mod oldest;
+pub mod position_by_hash;
 pub mod position_by_osu_id;
 pub use bulk_insert::*;
+pub use by_hash::*;
+pub use claim::*;
 pub use count::*;
 pub use delete::*;
 pub use insert::*;
-pub use oldest::*;
+pub use position_by_hash::*;
 pub use position_by_osu_id::*;
\ No newline at end of file
@@ -0,0 +1,22 @@
+use sqlx::{Error as SqlxError, PgPool};
+
+/// Position dans l'ordre effectif de traitement (priorité, puis ancienneté)
+pub async fn position_by_hash(pool: &PgPool, hash: &str) -> Result<Option<i64>, SqlxError> {
+    let position = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT position
+        FROM (
+            SELECT 
+                hash,
+                ROW_NUMBER() OVER (ORDER BY priority DESC, created_at ASC, id ASC) as position
+            FROM pending_beatmap
+        ) ranked
+        WHERE hash = $1
+        "#
+    )
+    .bind(hash)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(position)
+}
@@ -1,6 +1,7 @@
 use crate::models::pending_beatmap::PendingBeatmap;
 use sqlx::{Error as SqlxError, PgPool};
 
+/// Position dans l'ordre effectif de traitement (priorité, puis ancienneté)
 pub async fn position_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<i64>, SqlxError> {
     let position = sqlx::query_scalar::<_, i64>(
         r#"
@@ -8,7 +9,7 @@ pub async fn position_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<i64
         FROM (
             SELECT 
                 osu_id,
-                ROW_NUMBER() OVER (ORDER BY created_at ASC, id ASC) as position
+                ROW_NUMBER() OVER (ORDER BY priority DESC, created_at ASC, id ASC) as position
             FROM pending_beatmap
         ) ranked
         WHERE osu_id = $1
@@ -1,9 +1,23 @@
 use chrono::NaiveDateTime;
 
+/// File d'attente : les demandes interactives passent avant les imports en masse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingPriority {
+    Bulk = 0,
+    Interactive = 10,
+}
+
+impl PendingPriority {
+    pub fn value(self) -> i16 {
+        self as i16
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PendingBeatmap {
     pub id: i32,
     pub hash: String,
     pub osu_id: Option<i32>,
     pub created_at: Option<NaiveDateTime>,
+    pub attempts: i32,
 }
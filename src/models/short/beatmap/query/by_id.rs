@@ -5,7 +5,7 @@ pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<BeatmapShort>,
     sqlx::query_as!(
         BeatmapShort,
         r#"
-        SELECT id, osu_id, difficulty, difficulty_rating, mode, status
+        SELECT id, osu_id, difficulty, difficulty_rating, mode, key_count, status
         FROM beatmap
         WHERE id = $1
         "#,
@@ -19,7 +19,7 @@ pub async fn find_by_osu_id(pool: &PgPool, osu_id: i32) -> Result<Option<Beatmap
     sqlx::query_as!(
         BeatmapShort,
         r#"
-        SELECT id, osu_id, difficulty, difficulty_rating, mode, status
+        SELECT id, osu_id, difficulty, difficulty_rating, mode, key_count, status
         FROM beatmap
         WHERE osu_id = $1
         "#,
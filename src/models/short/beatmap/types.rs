@@ -8,5 +8,6 @@ pub struct BeatmapShort {
     pub difficulty: String,
     pub difficulty_rating: BigDecimal,
     pub mode: i32,
+    pub key_count: Option<i32>,
     pub status: String,
 }
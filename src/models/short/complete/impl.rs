@@ -1,5 +1,5 @@
 use crate::models::short::complete::query::{count_by_filters, find_by_beatmapset_id, find_by_beatmapset_osu_id, find_by_filters, random_by_filters};
-use crate::models::short::complete::types::{BeatmapCompleteShort, BeatmapsetCompleteShort};
+use crate::models::short::complete::types::{BeatmapCompleteShort, BeatmapsetCompleteShort, BeatmapsetPage};
 use crate::models::Filters;
 use sqlx::PgPool;
 
@@ -23,7 +23,7 @@ impl BeatmapsetCompleteShort {
     pub async fn find_by_filters(
         pool: &PgPool,
         filters: &Filters,
-    ) -> Result<Vec<Self>, sqlx::Error> {
+    ) -> Result<BeatmapsetPage, sqlx::Error> {
         find_by_filters(pool, filters).await
     }
 
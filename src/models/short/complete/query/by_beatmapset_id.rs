@@ -1,6 +1,7 @@
 use crate::models::short::beatmap::BeatmapShort;
 use crate::models::short::complete::types::BeatmapCompleteShort;
 use crate::models::short::msd::MSDShort;
+use crate::models::MsdPattern;
 use sqlx::{Error as SqlxError, PgPool};
 
 pub async fn find_by_beatmapset_id(
@@ -10,8 +11,10 @@ pub async fn find_by_beatmapset_id(
     let query = sqlx::query!(
         r#"
         SELECT 
-            b.id, b.osu_id, b.difficulty, b.difficulty_rating, b.mode, b.status,
-            m.id as "msd_id?", m.overall, m.main_pattern
+            b.id, b.osu_id, b.difficulty, b.difficulty_rating, b.mode, b.key_count, b.status,
+            m.id as "msd_id?", m.overall, m.rate, m.main_pattern,
+            m.primary_pattern as "primary_pattern: MsdPattern",
+            m.secondary_pattern as "secondary_pattern: MsdPattern"
         FROM beatmap b
         LEFT JOIN msd m ON b.id = m.beatmap_id
         WHERE b.beatmapset_id = $1
@@ -30,13 +33,18 @@ pub async fn find_by_beatmapset_id(
                 difficulty: r.difficulty,
                 difficulty_rating: r.difficulty_rating,
                 mode: r.mode,
+                key_count: r.key_count,
                 status: r.status,
             }),
             msd: r.msd_id.map(|id| MSDShort {
                 id: Some(id),
                 overall: r.overall,
+                rate: r.rate.clone(),
                 main_pattern: r.main_pattern,
+                primary_pattern: r.primary_pattern,
+                secondary_pattern: r.secondary_pattern,
             }),
+            matched_rates: r.rate.into_iter().collect(),
         })
         .collect())
 }
@@ -1,12 +1,14 @@
-use crate::models::short::complete::types::BeatmapsetCompleteShort;
+use crate::models::cursor::Cursor;
+use crate::models::short::complete::types::BeatmapsetPage;
 use crate::models::Filters;
-use sqlx::{Error as SqlxError, PgPool};
+use sqlx::{Error as SqlxError, PgPool, Row};
+use std::collections::HashSet;
 use super::common::{build_query_with_filters, bind_filter_params, bind_pagination_params, QueryType, map_rows_to_beatmapsets};
 
 pub async fn find_by_filters(
     pool: &PgPool,
     filters: &Filters,
-) -> Result<Vec<BeatmapsetCompleteShort>, SqlxError> {
+) -> Result<BeatmapsetPage, SqlxError> {
 
     let query_builder = build_query_with_filters(QueryType::Select, filters);
     let mut query = sqlx::query(&query_builder.query);
@@ -14,9 +16,27 @@ pub async fn find_by_filters(
     query = bind_pagination_params(query, filters);
     let rows = query.fetch_all(pool).await?;
 
-    Ok(map_rows_to_beatmapsets(rows))
-}
-
-
-
+    // Page pleine : la suite reprend après le dernier set, dernier aussi dans l'ordre des lignes
+    let per_page = filters.per_page.unwrap_or(10);
+    let sets: HashSet<i32> = rows
+        .iter()
+        .filter_map(|row| row.try_get("beatmapset_id").ok())
+        .collect();
+    let next_cursor = match rows.last() {
+        Some(last) if sets.len() >= per_page => Some(
+            Cursor {
+                sort_by: filters.resolved_sort_by(),
+                sort_dir: filters.resolved_sort_dir(),
+                key: last.try_get("page_sort_key")?,
+                id: last.try_get("beatmapset_id")?,
+            }
+            .encode(),
+        ),
+        _ => None,
+    };
 
+    Ok(BeatmapsetPage {
+        beatmaps: map_rows_to_beatmapsets(rows),
+        next_cursor,
+    })
+}
@@ -1,6 +1,7 @@
-use crate::models::Filters;
+use crate::models::{Filters, SortDir};
 use crate::helpers::common::from_f64;
-use sqlx::postgres::PgArguments;
+use crate::helpers::search::SearchQuery;
+use sqlx::postgres::{PgArguments, PgRow};
 use crate::models::short::complete::types::{BeatmapCompleteShort, BeatmapsetCompleteShort};
 use crate::models::short::msd::MSDShort;
 use crate::models::short::beatmap::BeatmapShort;
@@ -31,10 +32,12 @@ pub fn build_base_query(query_type: &QueryType) -> String {
         QueryType::Select => String::from(
             r#"
         SELECT 
+            p.sort_key::text as page_sort_key,
             bs.id as beatmapset_id, bs.osu_id as beatmapset_osu_id, bs.artist, bs.title, bs.creator, bs.cover_url,
-            b.id as beatmap_id, b.osu_id as beatmap_osu_id, b.difficulty, b.difficulty_rating, b.mode, b.status,
-            m.id as msd_id, m.overall, m.main_pattern
-        FROM beatmapset bs
+            b.id as beatmap_id, b.osu_id as beatmap_osu_id, b.difficulty, b.difficulty_rating, b.mode, b.key_count, b.status,
+            m.id as msd_id, m.overall, m.rate, m.main_pattern, m.primary_pattern, m.secondary_pattern
+        FROM page p
+        JOIN beatmapset bs ON bs.id = p.id
         LEFT JOIN beatmap b ON bs.id = b.beatmapset_id
         LEFT JOIN msd m ON b.id = m.beatmap_id
             "#
@@ -51,8 +54,8 @@ pub fn build_base_query(query_type: &QueryType) -> String {
             r#"
         SELECT 
             bs.id as beatmapset_id, bs.osu_id as beatmapset_osu_id, bs.artist, bs.title, bs.creator, bs.cover_url,
-            b.id as beatmap_id, b.osu_id as beatmap_osu_id, b.difficulty, b.difficulty_rating, b.mode, b.status,
-            m.id as msd_id, m.overall, m.main_pattern
+            b.id as beatmap_id, b.osu_id as beatmap_osu_id, b.difficulty, b.difficulty_rating, b.mode, b.key_count, b.status,
+            m.id as msd_id, m.overall, m.rate, m.main_pattern, m.primary_pattern, m.secondary_pattern
         FROM beatmapset bs
         LEFT JOIN beatmap b ON bs.id = b.beatmapset_id
         LEFT JOIN msd m ON b.id = m.beatmap_id
@@ -66,14 +69,16 @@ pub fn build_where_conditions(filters: &Filters) -> (Vec<String>, usize) {
     let mut conditions: Vec<String> = Vec::new();
     let mut param_count = 0;
 
-    // Recherche par terme
+    // Recherche : mots libres en plein texte (toujours $1, repris par le tri par pertinence), puis champs qualifiés
     if let Some(search_term) = &filters.search_term {
-        if !search_term.is_empty() {
+        let search = SearchQuery::parse(search_term);
+        if search.text.is_some() {
             param_count += 1;
-            conditions.push(format!(
-                "(b.difficulty ILIKE ${} OR b.status ILIKE ${} OR bs.artist ILIKE ${} OR bs.title ILIKE ${} OR bs.creator ILIKE ${})",
-                param_count, param_count, param_count, param_count, param_count
-            ));
+            conditions.push(format!("bs.search_vector @@ to_tsquery('simple', ${})", param_count));
+        }
+        for (field, _) in &search.fields {
+            param_count += 1;
+            conditions.push(field.as_condition(param_count));
         }
     }
 
@@ -103,7 +108,44 @@ pub fn build_where_conditions(filters: &Filters) -> (Vec<String>, usize) {
             }
 
             param_count += 1;
-            conditions.push(format!("m.main_pattern ILIKE ${}", param_count));
+            conditions.push(format!(
+                "(m.primary_pattern = ${} OR m.secondary_pattern = ${})",
+                param_count, param_count
+            ));
+        }
+    }
+
+    if let Some(_secondary_pattern) = filters.secondary_pattern {
+        param_count += 1;
+        conditions.push(format!("m.secondary_pattern = ${}", param_count));
+    }
+
+    // Exclusion de skillsets dominants
+    if !filters.excluded_patterns.is_empty() {
+        param_count += 1;
+        conditions.push(format!("m.primary_pattern <> ALL(${})", param_count));
+    }
+
+    // Filtre par dominance du premier skillset sur le second
+    if let Some(_dominance_min) = filters.pattern_dominance_min {
+        param_count += 1;
+        conditions.push(format!("m.pattern_dominance >= ${}", param_count));
+    }
+
+    if let Some(_dominance_max) = filters.pattern_dominance_max {
+        param_count += 1;
+        conditions.push(format!("m.pattern_dominance <= ${}", param_count));
+    }
+
+    // Filtres par skillset
+    for (pattern, min, max) in filters.skillset_ranges() {
+        if min.is_some() {
+            param_count += 1;
+            conditions.push(format!("m.{} >= ${}", pattern.as_column_name(), param_count));
+        }
+        if max.is_some() {
+            param_count += 1;
+            conditions.push(format!("m.{} <= ${}", pattern.as_column_name(), param_count));
         }
     }
 
@@ -129,8 +171,77 @@ pub fn build_where_conditions(filters: &Filters) -> (Vec<String>, usize) {
         conditions.push(format!("b.total_time <= ${}", param_count));
     }
 
-    // Condition par défaut pour le rate
-    conditions.push("m.rate = 1.0".to_string());
+    // Filtre par part de LN
+    if let Some(_ln_ratio_min) = filters.ln_ratio_min {
+        param_count += 1;
+        conditions.push(format!("b.ln_ratio >= ${}", param_count));
+    }
+
+    if let Some(_ln_ratio_max) = filters.ln_ratio_max {
+        param_count += 1;
+        conditions.push(format!("b.ln_ratio <= ${}", param_count));
+    }
+
+    // Filtre par OD / HP
+    if let Some(_od_min) = filters.od_min {
+        param_count += 1;
+        conditions.push(format!("b.od >= ${}", param_count));
+    }
+
+    if let Some(_od_max) = filters.od_max {
+        param_count += 1;
+        conditions.push(format!("b.od <= ${}", param_count));
+    }
+
+    if let Some(_hp_min) = filters.hp_min {
+        param_count += 1;
+        conditions.push(format!("b.hp >= ${}", param_count));
+    }
+
+    if let Some(_hp_max) = filters.hp_max {
+        param_count += 1;
+        conditions.push(format!("b.hp <= ${}", param_count));
+    }
+
+    // Filtre par nombre de touches
+    if let Some(_key_count_min) = filters.key_count_min {
+        param_count += 1;
+        conditions.push(format!("b.key_count >= ${}", param_count));
+    }
+
+    if let Some(_key_count_max) = filters.key_count_max {
+        param_count += 1;
+        conditions.push(format!("b.key_count <= ${}", param_count));
+    }
+
+    // Filtre par statut et mode
+    if let Some(_status) = filters.status {
+        param_count += 1;
+        conditions.push(format!("b.status = ${}", param_count));
+    }
+
+    if let Some(_mode) = filters.mode {
+        param_count += 1;
+        conditions.push(format!("b.mode = ${}", param_count));
+    }
+
+    // Filtre par rate : exact, plage, ou tous les rates ; 1.0 par défaut,
+    // sans écarter les beatmaps qui n'ont pas de MSD (autres keymodes)
+    if let Some(_rate) = filters.rate {
+        param_count += 1;
+        conditions.push(format!("m.rate = ${}", param_count));
+    } else if filters.rate_min.is_some() || filters.rate_max.is_some() {
+        if let Some(_rate_min) = filters.rate_min {
+            param_count += 1;
+            conditions.push(format!("m.rate >= ${}", param_count));
+        }
+        if let Some(_rate_max) = filters.rate_max {
+            param_count += 1;
+            conditions.push(format!("m.rate <= ${}", param_count));
+        }
+    } else if !filters.any_rate.unwrap_or(false) {
+        conditions.push("(m.rate = 1.0 OR m.id IS NULL)".to_string());
+    }
 
     (conditions, param_count)
 }
@@ -149,17 +260,17 @@ pub fn build_query_with_filters(query_type: QueryType, filters: &Filters) -> Que
     // Ajouter ORDER BY et LIMIT selon le type de requête
     match &query_type {
         QueryType::Select => {
-            query.push_str(" ORDER BY bs.id, b.id");
-            
-            // Ajouter la pagination
-            let per_page = filters.per_page.unwrap_or(10);
-            let page = filters.page.unwrap_or(1);
-            let _offset = (page - 1) * per_page;
-            
-            param_count += 1;
-            query.push_str(&format!(" LIMIT ${}", param_count));
-            param_count += 1;
-            query.push_str(&format!(" OFFSET ${}", param_count));
+            // La pagination porte sur les beatmapsets, choisis d'abord dans le CTE `page`
+            let page = build_page_cte(filters, &conditions, &mut param_count);
+            query = format!("WITH page AS ({}) {}", page, query);
+
+            // Les beatmaps sans MSD (autres keymodes) restent en fin de liste dans les deux sens
+            let dir = filters.resolved_sort_dir().as_sql();
+            query.push_str(&format!(" ORDER BY p.sort_key {} NULLS LAST, p.id", dir));
+            if let Some(sort_by) = filters.resolved_sort_by() {
+                query.push_str(&format!(", {} {} NULLS LAST", sort_by.as_order_expr(), dir));
+            }
+            query.push_str(", b.id, m.rate");
         },
         QueryType::Random => {
             query.push_str(" ORDER BY RANDOM()");
@@ -173,6 +284,79 @@ pub fn build_query_with_filters(query_type: QueryType, filters: &Filters) -> Que
     QueryBuilder { query, param_count }
 }
 
+/// Sélectionne les ids des beatmapsets de la page, avec leur clé de tri :
+/// un set est classé selon sa meilleure difficulté dans le sens du tri
+fn build_page_cte(filters: &Filters, conditions: &[String], param_count: &mut usize) -> String {
+    let dir = filters.resolved_sort_dir();
+    let sort_key = match filters.resolved_sort_by() {
+        Some(sort_by) => format!(
+            "{}({})",
+            if dir == SortDir::Desc { "MAX" } else { "MIN" },
+            sort_by.as_order_expr()
+        ),
+        None => String::from("NULL::text"),
+    };
+
+    let mut cte = format!(
+        r#"
+        SELECT bs.id, {} as sort_key
+        FROM beatmapset bs
+        LEFT JOIN beatmap b ON bs.id = b.beatmapset_id
+        LEFT JOIN msd m ON b.id = m.beatmap_id
+        "#,
+        sort_key
+    );
+    // Curseur : les sets strictement après (clé, id) du dernier set renvoyé, clés nulles en dernier
+    let mut where_conditions = conditions.to_vec();
+    let mut having = None;
+    if let Some(cursor) = &filters.cursor {
+        match (filters.resolved_sort_by(), &cursor.key) {
+            (Some(sort_by), Some(_)) => {
+                *param_count += 1;
+                let key = format!("${}::{}", param_count, sort_by.sql_type());
+                *param_count += 1;
+                let cmp = if dir == SortDir::Desc { "<" } else { ">" };
+                having = Some(format!(
+                    "({k} {cmp} {key} OR ({k} = {key} AND bs.id > ${id}) OR {k} IS NULL)",
+                    k = sort_key,
+                    cmp = cmp,
+                    key = key,
+                    id = param_count
+                ));
+            }
+            (Some(_), None) => {
+                *param_count += 1;
+                having = Some(format!("({} IS NULL AND bs.id > ${})", sort_key, param_count));
+            }
+            // Sans tri, l'id seul suffit : filtré avant le GROUP BY, par la clé primaire
+            (None, _) => {
+                *param_count += 1;
+                where_conditions.push(format!("bs.id > ${}", param_count));
+            }
+        }
+    }
+
+    if !where_conditions.is_empty() {
+        cte.push_str(" WHERE ");
+        cte.push_str(&where_conditions.join(" AND "));
+    }
+    cte.push_str(" GROUP BY bs.id");
+    if let Some(having) = having {
+        cte.push_str(" HAVING ");
+        cte.push_str(&having);
+    }
+
+    cte.push_str(&format!(" ORDER BY sort_key {} NULLS LAST, bs.id", dir.as_sql()));
+    *param_count += 1;
+    cte.push_str(&format!(" LIMIT ${}", param_count));
+    if filters.cursor.is_none() {
+        *param_count += 1;
+        cte.push_str(&format!(" OFFSET ${}", param_count));
+    }
+
+    cte
+}
+
 /// Bind les paramètres de filtres à une requête SQL
 pub fn bind_filter_params<'q>(
     mut query_builder: sqlx::query::Query<'q, sqlx::Postgres, PgArguments>, 
@@ -181,9 +365,12 @@ pub fn bind_filter_params<'q>(
     
     // Bind search term
     if let Some(search_term) = &filters.search_term {
-        if !search_term.is_empty() {
-            let search_pattern = format!("%{}%", search_term);
-            query_builder = query_builder.bind(search_pattern);
+        let search = SearchQuery::parse(search_term);
+        if let Some(text) = search.text {
+            query_builder = query_builder.bind(text);
+        }
+        for (field, value) in &search.fields {
+            query_builder = query_builder.bind(field.bind_value(value));
         }
     }
 
@@ -208,8 +395,36 @@ pub fn bind_filter_params<'q>(
                 query_builder = query_builder.bind(from_f64(pattern_max));
             }
 
-            let pattern_search = format!("%\"{}\"%", column_name);
-            query_builder = query_builder.bind(pattern_search);
+            query_builder = query_builder.bind(*pattern);
+        }
+    }
+
+    if let Some(secondary_pattern) = filters.secondary_pattern {
+        query_builder = query_builder.bind(secondary_pattern);
+    }
+
+    // Bind excluded patterns
+    if !filters.excluded_patterns.is_empty() {
+        let excluded: Vec<&str> = filters.excluded_patterns.iter().map(|p| p.as_column_name()).collect();
+        query_builder = query_builder.bind(excluded);
+    }
+
+    // Bind pattern dominance filters
+    if let Some(dominance_min) = filters.pattern_dominance_min {
+        query_builder = query_builder.bind(from_f64(dominance_min));
+    }
+
+    if let Some(dominance_max) = filters.pattern_dominance_max {
+        query_builder = query_builder.bind(from_f64(dominance_max));
+    }
+
+    // Bind skillset filters
+    for (_, min, max) in filters.skillset_ranges() {
+        if let Some(min) = min {
+            query_builder = query_builder.bind(from_f64(min));
+        }
+        if let Some(max) = max {
+            query_builder = query_builder.bind(from_f64(max));
         }
     }
 
@@ -231,6 +446,62 @@ pub fn bind_filter_params<'q>(
         query_builder = query_builder.bind(total_time_max as i32);
     }
 
+    // Bind LN ratio filters
+    if let Some(ln_ratio_min) = filters.ln_ratio_min {
+        query_builder = query_builder.bind(from_f64(ln_ratio_min));
+    }
+
+    if let Some(ln_ratio_max) = filters.ln_ratio_max {
+        query_builder = query_builder.bind(from_f64(ln_ratio_max));
+    }
+
+    // Bind OD / HP filters
+    if let Some(od_min) = filters.od_min {
+        query_builder = query_builder.bind(from_f64(od_min));
+    }
+
+    if let Some(od_max) = filters.od_max {
+        query_builder = query_builder.bind(from_f64(od_max));
+    }
+
+    if let Some(hp_min) = filters.hp_min {
+        query_builder = query_builder.bind(from_f64(hp_min));
+    }
+
+    if let Some(hp_max) = filters.hp_max {
+        query_builder = query_builder.bind(from_f64(hp_max));
+    }
+
+    // Bind key count filters
+    if let Some(key_count_min) = filters.key_count_min {
+        query_builder = query_builder.bind(key_count_min);
+    }
+
+    if let Some(key_count_max) = filters.key_count_max {
+        query_builder = query_builder.bind(key_count_max);
+    }
+
+    // Bind status and mode filters
+    if let Some(status) = filters.status {
+        query_builder = query_builder.bind(status.as_str());
+    }
+
+    if let Some(mode) = filters.mode {
+        query_builder = query_builder.bind(mode);
+    }
+
+    // Bind rate filters (msd.rate a deux décimales : 1.2 doit valoir 1.20, pas 1.19999…)
+    if let Some(rate) = filters.rate {
+        query_builder = query_builder.bind(from_f64(rate).round(2));
+    } else {
+        if let Some(rate_min) = filters.rate_min {
+            query_builder = query_builder.bind(from_f64(rate_min).round(2));
+        }
+        if let Some(rate_max) = filters.rate_max {
+            query_builder = query_builder.bind(from_f64(rate_max).round(2));
+        }
+    }
+
     query_builder
 }
 
@@ -240,55 +511,319 @@ pub fn bind_pagination_params<'q>(
     filters: &Filters
 ) -> sqlx::query::Query<'q, sqlx::Postgres, PgArguments> {
     let per_page = filters.per_page.unwrap_or(10);
-    let page = filters.page.unwrap_or(1);
-    let offset = (page - 1) * per_page;
-    
-    query_builder = query_builder.bind(per_page as i64);
-    query_builder = query_builder.bind(offset as i64);
+
+    // Même ordre que build_page_cte : curseur, puis LIMIT, puis OFFSET sans curseur
+    if let Some(cursor) = &filters.cursor {
+        if let (Some(_), Some(key)) = (filters.resolved_sort_by(), &cursor.key) {
+            query_builder = query_builder.bind(key.clone());
+        }
+        query_builder = query_builder.bind(cursor.id);
+        query_builder = query_builder.bind(per_page as i64);
+    } else {
+        let page = filters.page.unwrap_or(1);
+        let offset = (page - 1) * per_page;
+
+        query_builder = query_builder.bind(per_page as i64);
+        query_builder = query_builder.bind(offset as i64);
+    }
 
     query_builder
 }
 
+/// Une ligne jointe beatmapset / beatmap / msd des requêtes Select et Random
+#[derive(Debug, Clone)]
+pub struct FilteredRow {
+    pub beatmapset: BeatmapsetShort,
+    pub beatmap: Option<BeatmapShort>,
+    pub msd: Option<MSDShort>,
+}
+
+impl FilteredRow {
+    /// `None` pour une ligne sans beatmapset
+    fn from_pg_row(row: &PgRow) -> Option<Self> {
+        let bs_id: i32 = row.try_get::<Option<i32>, _>("beatmapset_id").ok().flatten()?;
+
+        let beatmap = row.try_get::<Option<i32>, _>("beatmap_id").ok().flatten().map(|b_id| BeatmapShort {
+            id: Some(b_id),
+            osu_id: row.try_get("beatmap_osu_id").unwrap_or_default(),
+            difficulty: row.try_get("difficulty").unwrap_or_default(),
+            difficulty_rating: row.try_get("difficulty_rating").unwrap_or_default(),
+            mode: row.try_get("mode").unwrap_or_default(),
+            key_count: row.try_get("key_count").unwrap_or_default(),
+            status: row.try_get("status").unwrap_or_default(),
+        });
+        let msd = row.try_get::<Option<i32>, _>("msd_id").ok().flatten().map(|msd_id| MSDShort {
+            id: Some(msd_id),
+            overall: row.try_get("overall").unwrap_or_default(),
+            rate: row.try_get("rate").unwrap_or_default(),
+            main_pattern: row.try_get("main_pattern").unwrap_or_default(),
+            primary_pattern: row.try_get("primary_pattern").unwrap_or_default(),
+            secondary_pattern: row.try_get("secondary_pattern").unwrap_or_default(),
+        });
+
+        Some(Self {
+            beatmapset: BeatmapsetShort {
+                id: Some(bs_id),
+                osu_id: row.try_get("beatmapset_osu_id").unwrap_or_default(),
+                artist: row.try_get("artist").unwrap_or_default(),
+                title: row.try_get("title").unwrap_or_default(),
+                creator: row.try_get("creator").unwrap_or_default(),
+                cover_url: row.try_get("cover_url").unwrap_or_default(),
+            },
+            beatmap,
+            msd,
+        })
+    }
+}
+
 /// Transformer les lignes SQL en structures Rust
-pub fn map_rows_to_beatmapsets(rows: Vec<sqlx::postgres::PgRow>) -> Vec<BeatmapsetCompleteShort> {
-    let mut map: std::collections::HashMap<i32, BeatmapsetCompleteShort> = HashMap::new();
+pub fn map_rows_to_beatmapsets(rows: Vec<PgRow>) -> Vec<BeatmapsetCompleteShort> {
+    group_rows(rows.iter().filter_map(FilteredRow::from_pg_row))
+}
+
+/// Regroupe les lignes par beatmapset puis par beatmap, dans l'ordre de première apparition :
+/// l'ORDER BY de la requête décide de l'ordre des sets et des difficultés
+pub fn group_rows(rows: impl IntoIterator<Item = FilteredRow>) -> Vec<BeatmapsetCompleteShort> {
+    let mut sets: Vec<BeatmapsetCompleteShort> = Vec::new();
+    let mut positions: HashMap<i32, usize> = HashMap::new();
 
     for row in rows {
-        let beatmapset_id: Option<i32> = row.try_get("beatmapset_id").ok();
-        if let Some(bs_id) = beatmapset_id {
-            let entry = map.entry(bs_id).or_insert_with(|| BeatmapsetCompleteShort {
-                beatmapset: Some(BeatmapsetShort {
-                    id: Some(bs_id),
-                    osu_id: row.try_get("beatmapset_osu_id").unwrap_or_default(),
-                    artist: row.try_get("artist").unwrap_or_default(),
-                    title: row.try_get("title").unwrap_or_default(),
-                    creator: row.try_get("creator").unwrap_or_default(),
-                    cover_url: row.try_get("cover_url").unwrap_or_default(),
-                }),
+        let Some(bs_id) = row.beatmapset.id else { continue };
+        let position = *positions.entry(bs_id).or_insert_with(|| {
+            sets.push(BeatmapsetCompleteShort {
+                beatmapset: Some(row.beatmapset.clone()),
                 beatmap: Vec::new(),
             });
+            sets.len() - 1
+        });
+        let entry = &mut sets[position];
+
+        // Beatmapset sans difficulté retenue (LEFT JOIN)
+        let Some(beatmap) = row.beatmap else { continue };
+        let rate = row.msd.as_ref().and_then(|m| m.rate.clone());
+
+        // Une ligne par rate retenu : la beatmap garde la MSD du premier, et la liste des rates
+        let existing = entry
+            .beatmap
+            .iter_mut()
+            .find(|b| b.beatmap.as_ref().and_then(|b| b.id) == beatmap.id);
+        if let Some(existing) = existing {
+            existing.matched_rates.extend(rate);
+            continue;
+        }
 
-            if let Ok(beatmap_id) = row.try_get::<Option<i32>, _>("beatmap_id") {
-                if let Some(b_id) = beatmap_id {
-                    entry.beatmap.push(BeatmapCompleteShort {
-                        beatmap: Some(BeatmapShort {
-                            id: Some(b_id),
-                            osu_id: row.try_get("beatmap_osu_id").unwrap_or_default(),
-                            difficulty: row.try_get("difficulty").unwrap_or_default(),
-                            difficulty_rating: row.try_get("difficulty_rating").unwrap_or_default(),
-                            mode: row.try_get("mode").unwrap_or_default(),
-                            status: row.try_get("status").unwrap_or_default(),
-                        }),
-                        msd: row.try_get::<Option<i32>, _>("msd_id").ok().flatten().map(|msd_id| MSDShort {
-                            id: Some(msd_id),
-                            overall: row.try_get("overall").unwrap_or_default(),
-                            main_pattern: row.try_get("main_pattern").unwrap_or_default(),
-                        }),
-                    });
-                }
-            }
+        entry.beatmap.push(BeatmapCompleteShort {
+            beatmap: Some(beatmap),
+            msd: row.msd,
+            matched_rates: rate.into_iter().collect(),
+        });
+    }
+
+    sets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+    use sqlx::{Arguments, Execute};
+    use std::str::FromStr;
+
+    fn filters(json: &str) -> Filters {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn row(set_id: i32, beatmap_id: Option<i32>, rate: Option<&str>) -> FilteredRow {
+        FilteredRow {
+            beatmapset: BeatmapsetShort {
+                id: Some(set_id),
+                osu_id: Some(set_id * 100),
+                artist: format!("artist {}", set_id),
+                title: format!("title {}", set_id),
+                creator: String::from("creator"),
+                cover_url: None,
+            },
+            beatmap: beatmap_id.map(|id| BeatmapShort {
+                id: Some(id),
+                osu_id: Some(id * 100),
+                difficulty: format!("diff {}", id),
+                difficulty_rating: BigDecimal::from(3),
+                mode: 3,
+                key_count: Some(4),
+                status: String::from("ranked"),
+            }),
+            msd: rate.map(|rate| MSDShort {
+                id: Some(1),
+                overall: Some(BigDecimal::from(20)),
+                rate: Some(BigDecimal::from_str(rate).unwrap()),
+                main_pattern: None,
+                primary_pattern: None,
+                secondary_pattern: None,
+            }),
         }
     }
 
-    map.into_iter().map(|(_, v)| v).collect()
+    fn set_ids(sets: &[BeatmapsetCompleteShort]) -> Vec<i32> {
+        sets.iter().filter_map(|s| s.beatmapset.as_ref()?.id).collect()
+    }
+
+    fn beatmap_ids(set: &BeatmapsetCompleteShort) -> Vec<i32> {
+        set.beatmap.iter().filter_map(|b| b.beatmap.as_ref()?.id).collect()
+    }
+
+    #[test]
+    fn select_rows_keep_query_order() {
+        // Tri décroissant : les ids ne suivent pas l'ordre des lignes
+        let rows = vec![
+            row(7, Some(72), Some("1.0")),
+            row(7, Some(70), Some("1.0")),
+            row(2, Some(21), Some("1.0")),
+            row(5, Some(50), Some("1.0")),
+            row(5, Some(53), Some("1.0")),
+            row(5, Some(51), Some("1.0")),
+        ];
+
+        let sets = group_rows(rows);
+
+        assert_eq!(set_ids(&sets), vec![7, 2, 5]);
+        assert_eq!(beatmap_ids(&sets[0]), vec![72, 70]);
+        assert_eq!(beatmap_ids(&sets[1]), vec![21]);
+        assert_eq!(beatmap_ids(&sets[2]), vec![50, 53, 51]);
+    }
+
+    #[test]
+    fn select_rows_collapse_rates_into_first_msd() {
+        let rows = vec![
+            row(1, Some(10), Some("1.1")),
+            row(1, Some(10), Some("1.2")),
+            row(1, Some(11), Some("1.1")),
+            row(1, Some(10), Some("1.3")),
+        ];
+
+        let sets = group_rows(rows);
+
+        assert_eq!(beatmap_ids(&sets[0]), vec![10, 11]);
+        let first = &sets[0].beatmap[0];
+        assert_eq!(first.msd.as_ref().unwrap().rate, Some(BigDecimal::from_str("1.1").unwrap()));
+        let rates: Vec<String> = first.matched_rates.iter().map(|r| r.to_string()).collect();
+        assert_eq!(rates, vec!["1.1", "1.2", "1.3"]);
+    }
+
+    #[test]
+    fn select_rows_keep_sets_without_beatmaps() {
+        let rows = vec![row(3, None, None), row(4, Some(40), None)];
+
+        let sets = group_rows(rows);
+
+        assert_eq!(set_ids(&sets), vec![3, 4]);
+        assert!(sets[0].beatmap.is_empty());
+        assert_eq!(beatmap_ids(&sets[1]), vec![40]);
+        assert!(sets[1].beatmap[0].msd.is_none());
+        assert!(sets[1].beatmap[0].matched_rates.is_empty());
+    }
+
+    #[test]
+    fn random_rows_group_by_first_appearance() {
+        // ORDER BY RANDOM() mélange les lignes d'un même set
+        let rows = vec![
+            row(9, Some(91), Some("1.0")),
+            row(4, Some(40), Some("1.0")),
+            row(9, Some(90), Some("1.0")),
+            row(6, Some(60), Some("1.0")),
+            row(4, Some(42), Some("1.0")),
+            row(9, Some(91), Some("1.1")),
+        ];
+
+        let sets = group_rows(rows);
+
+        assert_eq!(set_ids(&sets), vec![9, 4, 6]);
+        assert_eq!(beatmap_ids(&sets[0]), vec![91, 90]);
+        assert_eq!(beatmap_ids(&sets[1]), vec![40, 42]);
+        assert_eq!(beatmap_ids(&sets[2]), vec![60]);
+        assert_eq!(sets[0].beatmap[0].matched_rates.len(), 2);
+    }
+
+    #[test]
+    fn grouping_is_stable_across_runs() {
+        let rows: Vec<FilteredRow> = (1..=50)
+            .rev()
+            .flat_map(|set| [row(set, Some(set * 10), Some("1.0")), row(set, Some(set * 10 + 1), Some("1.0"))])
+            .collect();
+
+        let first = set_ids(&group_rows(rows.clone()));
+        for _ in 0..10 {
+            assert_eq!(set_ids(&group_rows(rows.clone())), first);
+        }
+        assert_eq!(first, (1..=50).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn default_rate_keeps_beatmaps_without_msd() {
+        let (conditions, param_count) = build_where_conditions(&filters("{}"));
+
+        assert_eq!(conditions, vec!["(m.rate = 1.0 OR m.id IS NULL)"]);
+        assert_eq!(param_count, 0);
+    }
+
+    #[test]
+    fn explicit_rate_only_matches_msd_rows() {
+        let (conditions, _) = build_where_conditions(&filters(r#"{"rate": 1.1}"#));
+        assert_eq!(conditions, vec!["m.rate = $1"]);
+
+        let (conditions, _) = build_where_conditions(&filters(r#"{"any_rate": true}"#));
+        assert!(conditions.is_empty());
+    }
+
+    #[test]
+    fn select_rows_list_beatmaps_without_msd() {
+        // Une 7K n'a pas de MSD : la ligne arrive avec m.* à NULL
+        let mut seven_key = row(8, Some(80), None);
+        if let Some(beatmap) = seven_key.beatmap.as_mut() {
+            beatmap.key_count = Some(7);
+        }
+        let rows = vec![row(8, Some(81), Some("1.0")), seven_key];
+
+        let sets = group_rows(rows);
+
+        assert_eq!(beatmap_ids(&sets[0]), vec![81, 80]);
+        let listed = &sets[0].beatmap[1];
+        assert_eq!(listed.beatmap.as_ref().unwrap().key_count, Some(7));
+        assert!(listed.msd.is_none());
+    }
+
+    #[test]
+    fn unsorted_cursor_filters_before_grouping() {
+        let mut filters = filters("{}");
+        filters.cursor = Some(crate::models::cursor::Cursor {
+            sort_by: None,
+            sort_dir: filters.resolved_sort_dir(),
+            key: None,
+            id: 42,
+        });
+
+        let built = build_query_with_filters(QueryType::Select, &filters);
+
+        assert!(built.query.contains("WHERE (m.rate = 1.0 OR m.id IS NULL) AND bs.id > $1 GROUP BY bs.id"));
+        assert!(!built.query.contains("HAVING"));
+        assert_eq!(built.param_count, 2);
+    }
+
+    #[test]
+    fn excluded_patterns_apply_to_every_query_type() {
+        let filters = filters(r#"{"excluded_patterns": "chordjack, jackspeed"}"#);
+
+        for query_type in [QueryType::Select, QueryType::Count, QueryType::Random] {
+            let is_select = matches!(query_type, QueryType::Select);
+            let built = build_query_with_filters(query_type, &filters);
+            assert!(built.query.contains("m.primary_pattern <> ALL($1)"), "{}", built.query);
+
+            // Une seule valeur liée pour la liste, avant la pagination
+            let mut query = bind_filter_params(sqlx::query(&built.query), &filters);
+            if is_select {
+                query = bind_pagination_params(query, &filters);
+            }
+            let arguments = query.take_arguments().unwrap().unwrap();
+            assert_eq!(arguments.len(), built.param_count);
+        }
+    }
 }
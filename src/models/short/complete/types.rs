@@ -1,12 +1,17 @@
 use crate::models::short::beatmap::BeatmapShort;
 use crate::models::short::beatmapset::BeatmapsetShort;
 use crate::models::short::msd::MSDShort;
+use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BeatmapCompleteShort {
     pub beatmap: Option<BeatmapShort>,
+    /// MSD au premier rate retenu par les filtres (1.0 par défaut)
     pub msd: Option<MSDShort>,
+    /// Tous les rates retenus par les filtres pour cette beatmap
+    #[serde(default)]
+    pub matched_rates: Vec<BigDecimal>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -14,3 +19,11 @@ pub struct BeatmapsetCompleteShort {
     pub beatmapset: Option<BeatmapsetShort>,
     pub beatmap: Vec<BeatmapCompleteShort>,
 }
+
+/// Une page de la liste filtrée
+#[derive(Debug, Clone)]
+pub struct BeatmapsetPage {
+    pub beatmaps: Vec<BeatmapsetCompleteShort>,
+    /// Curseur de la page suivante, absent sur la dernière page
+    pub next_cursor: Option<String>,
+}
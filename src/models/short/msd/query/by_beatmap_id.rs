@@ -1,4 +1,5 @@
 use crate::models::short::msd::types::MSDShort;
+use crate::models::MsdPattern;
 use sqlx::{Error as SqlxError, PgPool};
 
 pub async fn find_by_beatmap_id(
@@ -8,7 +9,9 @@ pub async fn find_by_beatmap_id(
     let result = sqlx::query_as!(
         MSDShort,
         r#"
-        SELECT id, overall, main_pattern
+        SELECT id, overall, rate, main_pattern,
+               primary_pattern as "primary_pattern: MsdPattern",
+               secondary_pattern as "secondary_pattern: MsdPattern"
         FROM msd
         WHERE beatmap_id = $1
         ORDER BY id ASC
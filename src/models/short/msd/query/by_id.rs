@@ -1,11 +1,14 @@
 use crate::models::short::msd::types::MSDShort;
+use crate::models::MsdPattern;
 use sqlx::{Error as SqlxError, PgPool};
 
 pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<MSDShort>, SqlxError> {
     let result = sqlx::query_as!(
         MSDShort,
         r#"
-        SELECT id, overall, main_pattern
+        SELECT id, overall, rate, main_pattern,
+               primary_pattern as "primary_pattern: MsdPattern",
+               secondary_pattern as "secondary_pattern: MsdPattern"
         FROM msd
         WHERE id = $1
         "#,
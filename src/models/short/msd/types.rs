@@ -1,3 +1,4 @@
+use crate::models::MsdPattern;
 use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 
@@ -5,5 +6,8 @@ use serde::{Deserialize, Serialize};
 pub struct MSDShort {
     pub id: Option<i32>,
     pub overall: Option<BigDecimal>,
+    pub rate: Option<BigDecimal>,
     pub main_pattern: Option<String>,
+    pub primary_pattern: Option<MsdPattern>,
+    pub secondary_pattern: Option<MsdPattern>,
 }
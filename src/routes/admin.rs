@@ -0,0 +1,39 @@
+//! # Admin Routes Module
+//!
+//! Ce module configure les routes d'administration, protégées par `ADMIN_TOKEN`.
+
+use crate::config::AdminConfig;
+use crate::middleware::admin_auth::admin_auth_middleware;
+use crate::{db::DatabaseManager, handlers};
+use crate::services::osz_import::MAX_OSZ_BYTES;
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    middleware::from_fn_with_state,
+    routing::{get, post},
+};
+
+pub fn router(db: DatabaseManager, admin: AdminConfig) -> Router<DatabaseManager> {
+    Router::new()
+        .route(
+            "/admin/failed",
+            get(handlers::admin::get::failed::handler)
+                .delete(handlers::admin::delete::purge_failed::handler),
+        )
+        .route(
+            "/admin/failed/{hash}/retry",
+            post(handlers::admin::post::retry_failed::handler),
+        )
+        .route(
+            "/admin/import/osz",
+            post(handlers::admin::post::import_osz::handler)
+                .layer(DefaultBodyLimit::max(MAX_OSZ_BYTES + 64 * 1024)),
+        )
+        .route(
+            "/admin/recalc",
+            get(handlers::admin::get::recalc::handler)
+                .post(handlers::admin::post::recalc::handler),
+        )
+        .route_layer(from_fn_with_state(admin, admin_auth_middleware))
+        .with_state(db)
+}
@@ -0,0 +1,21 @@
+//! # Analyze Routes Module
+//!
+//! Ce module configure l'analyse de fichiers .osu envoyés directement.
+
+use crate::helpers::osu_file::MAX_OSU_FILE_BYTES;
+use crate::{db::DatabaseManager, handlers};
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    routing::post,
+};
+
+pub fn router() -> Router<DatabaseManager> {
+    Router::new()
+        .route(
+            "/analyze",
+            // Marge pour l'enveloppe multipart autour du fichier
+            post(handlers::analyze::post::osu_file::handler)
+                .layer(DefaultBodyLimit::max(MAX_OSU_FILE_BYTES + 64 * 1024)),
+        )
+}
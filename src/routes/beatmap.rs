@@ -26,6 +26,14 @@ pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
             "/beatmap/random",
             get(handlers::beatmap::get::random::handler),
         )
+        .route(
+            "/beatmap/{osu_id}/density",
+            get(handlers::beatmap::get::density::handler),
+        )
+        .route(
+            "/beatmap/{osu_id}/msd",
+            get(handlers::beatmap::get::msd::handler),
+        )
         .route(
             "/beatmapset/{id}",
             get(handlers::beatmap::get::by_id_extended::handler),
@@ -12,12 +12,15 @@
 //! 3. Ajoutez le module dans ce fichier
 //! 4. Utilisez `merge()` pour combiner les routes
 
+use crate::config::Config;
 use crate::db::DatabaseManager;
 use axum::{Router, routing::get};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 // Re-export all route modules here
+pub mod admin;
+pub mod analyze;
 pub mod beatmap;
 pub mod help;
 pub mod pending_beatmap;
@@ -30,14 +33,16 @@ pub mod pending_beatmap;
 ))]
 struct ApiDoc;
 
-pub fn create_router(db: DatabaseManager) -> Router {
+pub fn create_router(db: DatabaseManager, config: &Config) -> Router {
     Router::new()
         // Page de status principale à la racine
         .route("/", get(crate::handlers::status::page::status_page))
         // Routes API
         .nest("/api", beatmap::router(db.clone()))
         .nest("/api", help::router())
+        .nest("/api", analyze::router())
         .nest("/api", pending_beatmap::router(db.clone()))
+        .nest("/api", admin::router(db.clone(), config.admin.clone()))
         .merge(SwaggerUi::new("/api/swagger").url("/api-doc/openapi.json", ApiDoc::openapi()))
         // Add your other route modules here
         // Example:
@@ -15,5 +15,21 @@ pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
             "/pending_beatmap/status/{id}",
             get(handlers::pending_beatmap::get::status_by_osu_id::handler),
         )
+        .route(
+            "/pending_beatmap/status/hash/{hash}",
+            get(handlers::pending_beatmap::get::status_by_hash::handler),
+        )
+        .route(
+            "/pending_beatmap/events/{id}",
+            get(handlers::pending_beatmap::get::events::by_osu_id),
+        )
+        .route(
+            "/pending_beatmap/events/hash/{hash}",
+            get(handlers::pending_beatmap::get::events::by_hash),
+        )
+        .route(
+            "/pending_beatmap/batch/{batch_id}",
+            get(handlers::pending_beatmap::get::batch_by_id::handler),
+        )
         .with_state(db)
 }
@@ -0,0 +1,60 @@
+use crate::models::failed_query::FailureReason;
+use crate::services::osu_file_store::OsuFileError;
+use rosu_v2::error::OsuError;
+
+/// Erreur de traitement d'un checksum, classée pour décider si on retente
+#[derive(Debug, thiserror::Error)]
+pub enum ProcessError {
+    #[error("Checksum already processed or waiting for retry: {0}")]
+    AlreadyProcessed(String),
+    #[error("Beatmap not found: {0}")]
+    NotFound(String),
+    #[error("Beatmap not allowed: {0}")]
+    NotAllowedMode(String),
+    #[error("Failed to convert osu file to notes: {0}")]
+    Parse(String),
+    #[error("Network error: {0}")]
+    Network(String),
+    #[error("Failed to calculate MSD: {0}")]
+    Calc(String),
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl ProcessError {
+    /// Classe une erreur de l'API osu! : seules les 404 sont définitives
+    pub fn from_api(err: anyhow::Error) -> Self {
+        match err.downcast_ref::<OsuError>() {
+            Some(OsuError::NotFound) => ProcessError::NotFound(err.to_string()),
+            _ => ProcessError::Network(err.to_string()),
+        }
+    }
+
+    /// Raison enregistrée dans failed_query, `None` si l'échec ne doit pas être enregistré
+    pub fn reason(&self) -> Option<FailureReason> {
+        match self {
+            ProcessError::AlreadyProcessed(_) | ProcessError::Database(_) => None,
+            ProcessError::NotFound(_) => Some(FailureReason::NotFound),
+            ProcessError::NotAllowedMode(_) => Some(FailureReason::NotAllowedMode),
+            ProcessError::Parse(_) => Some(FailureReason::ParseError),
+            ProcessError::Network(_) => Some(FailureReason::Network),
+            ProcessError::Calc(_) => Some(FailureReason::CalcError),
+        }
+    }
+}
+
+impl From<OsuFileError> for ProcessError {
+    fn from(err: OsuFileError) -> Self {
+        match err {
+            OsuFileError::Download(_) => ProcessError::Network(err.to_string()),
+            // Le fichier en ligne ne correspond plus à ce checksum : il ne reviendra pas
+            OsuFileError::ChecksumMismatch { .. } => ProcessError::NotFound(err.to_string()),
+        }
+    }
+}
+
+impl From<sqlx::Error> for ProcessError {
+    fn from(err: sqlx::Error) -> Self {
+        ProcessError::Database(err.to_string())
+    }
+}
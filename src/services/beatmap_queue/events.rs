@@ -0,0 +1,31 @@
+use crate::models::failed_query::FailureReason;
+use once_cell::sync::Lazy;
+use tokio::sync::broadcast;
+
+/// Événements émis par les workers pendant le traitement de la file
+#[derive(Debug, Clone)]
+pub enum QueueEvent {
+    Claimed {
+        hash: String,
+    },
+    Processed {
+        hash: String,
+        beatmapset_id: Option<i32>,
+    },
+    Failed {
+        hash: String,
+        reason: Option<FailureReason>,
+    },
+}
+
+static QUEUE_EVENTS: Lazy<broadcast::Sender<QueueEvent>> =
+    Lazy::new(|| broadcast::channel(1024).0);
+
+/// Publie un événement, ignoré s'il n'y a aucun abonné
+pub fn publish(event: QueueEvent) {
+    let _ = QUEUE_EVENTS.send(event);
+}
+
+pub fn subscribe() -> broadcast::Receiver<QueueEvent> {
+    QUEUE_EVENTS.subscribe()
+}
@@ -1,63 +1,244 @@
 use crate::services::beatmap_queue::processor::BeatmapProcessor;
+use crate::models::beatmap_density::{BeatmapDensity, DENSITY_WINDOW_MS, DensityTimeline};
 use crate::models::extended::beatmap::BeatmapExtended;
 use crate::models::extended::beatmapset::BeatmapsetExtended;
 use crate::models::pending_beatmap::PendingBeatmap;
 use crate::services::msd_calculator::osu_to_notes;
-use crate::helpers::beatmap::osu_file_from_url;
 use crate::helpers::beatmap::is_allowed_beatmap;
 use crate::models::extended::msd::MSDExtended;
 use crate::services::osu_api::OsuApiService;
-use crate::models::failed_query::FailedQuery;
+use crate::services::osu_file_store::OsuFileStore;
+use crate::models::failed_query::{FailedQuery, FailureReason};
+use crate::services::beatmap_queue::error::ProcessError;
+use crate::services::beatmap_queue::events::{self, QueueEvent};
 use anyhow::Result;
+use rosu_v2::model::beatmap::BeatmapExtended as BmExtended;
+use std::collections::HashSet;
 use tracing::{info, error};
 
-pub async fn handle_pending(pending: &PendingBeatmap) -> Result<()> {
+/// Une difficulté prête à être insérée : métadonnées, MSD par rate et timeline de densité
+pub struct PreparedBeatmap {
+    pub beatmap: BeatmapExtended,
+    pub msd: Vec<MSDExtended>,
+    pub density: DensityTimeline,
+}
+
+/// Délai avant de reprendre un checksum après une erreur DB, doublé à chaque tentative
+const DATABASE_RETRY_BASE_SECS: u64 = 30;
+
+pub async fn handle_pending(pending: &PendingBeatmap, max_attempts: i32) -> Result<()> {
     let processor = BeatmapProcessor::instance();
-    let db_ref = processor.db.as_ref().ok_or_else(|| anyhow::anyhow!("Database not initialized"));
-    let _ = PendingBeatmap::delete_by_hash(db_ref?.get_pool(), &pending.hash).await.map_err(|e| error!("Failed to delete pending beatmap: {}", e));
-    match processor.process_single_checksum(pending.hash.clone()).await {
+    let db_ref = processor.db.as_ref().ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
+    let pool = db_ref.get_pool();
+
+    // Le bail a déjà expiré plusieurs fois : ce checksum fait sûrement tomber le process
+    if pending.attempts > max_attempts {
+        let message = format!("Abandoned after {} unfinished attempts", pending.attempts - 1);
+        FailedQuery::insert(pool, &pending.hash, FailureReason::LeaseExpired, Some(&message)).await?;
+        PendingBeatmap::delete_by_id(pool, pending.id).await?;
+        events::publish(QueueEvent::Failed {
+            hash: pending.hash.clone(),
+            reason: Some(FailureReason::LeaseExpired),
+        });
+        error!("Checksum {} abandoned after {} attempts", pending.hash, pending.attempts - 1);
+        return Err(anyhow::anyhow!("Too many attempts for checksum: {}", pending.hash));
+    }
+
+    events::publish(QueueEvent::Claimed { hash: pending.hash.clone() });
+
+    let result = processor.process_single_checksum(pending.hash.clone()).await;
+    match &result {
         Ok(_) => {
+            // Une tentative réussie efface l'historique d'échec
+            FailedQuery::delete_by_hash(pool, &pending.hash).await?;
             info!("Beatmap processed with success: {}", pending.hash);
-            Ok(())
+        }
+        Err(ProcessError::Database(e)) => {
+            // Erreur DB : on garde la ligne et on la rend réclamable après un backoff,
+            // sans attendre l'expiration du bail (si la base répond encore)
+            error!("Database error processing checksum {}: {}", pending.hash, e);
+            let delay = DATABASE_RETRY_BASE_SECS << (pending.attempts - 1).clamp(0, 6);
+            if let Err(release_error) = PendingBeatmap::release_lease(pool, pending.id, delay).await {
+                error!("Failed to release lease of checksum {}: {}", pending.hash, release_error);
+            }
+            return Err(anyhow::anyhow!("Database error processing checksum {}: {}", pending.hash, e));
         }
         Err(e) => {
-            let db_ref = processor.db.as_ref().ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
-            let _ = FailedQuery::insert(db_ref.get_pool(), &pending.hash).await.map_err(|e| error!("Failed to insert failed query: {}", e));
+            // Si l'échec ne peut pas être enregistré, on garde la ligne : elle sera reprise à l'expiration du bail
+            if let Some(reason) = e.reason() {
+                FailedQuery::insert(pool, &pending.hash, reason, Some(&e.to_string())).await?;
+            }
             error!("Error processing checksum {}: {}", pending.hash, e);
-            Err(e)
         }
     }
+
+    PendingBeatmap::delete_by_id(pool, pending.id).await?;
+
+    // Publié après la suppression pour que les abonnés relisent un état définitif
+    events::publish(match &result {
+        Ok(beatmapset_id) => QueueEvent::Processed {
+            hash: pending.hash.clone(),
+            beatmapset_id: *beatmapset_id,
+        },
+        Err(e) => QueueEvent::Failed {
+            hash: pending.hash.clone(),
+            reason: e.reason(),
+        },
+    });
+
+    result.map(|_| ()).map_err(anyhow::Error::from)
 }
 
 impl BeatmapProcessor {
-    pub async fn process_single_checksum(&self, checksum: String) -> Result<()> {
+    /// Traite un checksum et les autres difficultés autorisées de son beatmapset.
+    ///
+    /// Retourne l'id osu! du beatmapset. Le beatmapset n'est inséré qu'une fois,
+    /// l'échec d'une autre difficulté est enregistré sans bloquer celle demandée.
+    pub async fn process_single_checksum(&self, checksum: String) -> Result<Option<i32>, ProcessError> {
         if self.is_already_processed(checksum.clone()).await? {
-            return Err(anyhow::anyhow!("Checksum has already been processed: {}", checksum));
+            return Err(ProcessError::AlreadyProcessed(checksum));
         }
 
+        let db_ref = self.db.as_ref().ok_or_else(|| ProcessError::Database("Database not initialized".to_string()))?;
+        let pool = db_ref.get_pool();
+
         let osu_api = OsuApiService::instance();
-        let beatmap_extended = osu_api.beatmap_by_checksum(checksum.clone()).await?;
+        let beatmap_extended = osu_api
+            .beatmap_by_checksum(checksum.clone())
+            .await
+            .map_err(ProcessError::from_api)?;
         if !is_allowed_beatmap(beatmap_extended.mode, beatmap_extended.cs).await 
         {
-            return Err(anyhow::anyhow!("Beatmap not allowed"));
+            return Err(ProcessError::NotAllowedMode(format!(
+                "mode {:?}, cs {}",
+                beatmap_extended.mode, beatmap_extended.cs
+            )));
+        }
+
+        let mut mapset = osu_api
+            .beatmapset_by_id(beatmap_extended.mapset_id)
+            .await
+            .map_err(ProcessError::from_api)?;
+        let maps = mapset.maps.take().unwrap_or_default();
+        let mut beatmapset = BeatmapsetExtended::from(mapset);
+
+        let siblings = self.pending_siblings(&checksum, maps).await?;
+
+        // La difficulté demandée d'abord : si elle échoue, les autres sont quand même insérées
+        let mut prepared = Vec::new();
+        let requested = self.prepare_beatmap(beatmap_extended).await;
+        for sibling in siblings {
+            let hash = sibling.checksum.clone().unwrap_or_default();
+            match self.prepare_beatmap(sibling).await {
+                Ok(beatmap) => prepared.push(beatmap),
+                Err(e) => {
+                    if let Some(reason) = e.reason() {
+                        FailedQuery::insert(pool, &hash, reason, Some(&e.to_string())).await?;
+                    }
+                    error!("Error processing sibling {} of {}: {}", hash, checksum, e);
+                }
+            }
+        }
+
+        let requested = match requested {
+            Ok(beatmap) => {
+                prepared.insert(0, beatmap);
+                Ok(beatmapset.osu_id)
+            }
+            Err(e) => Err(e),
+        };
+
+        if !prepared.is_empty() {
+            self.insert_into_db(&mut beatmapset, &mut prepared)
+                .await
+                .map_err(|e| ProcessError::Database(e.to_string()))?;
+
+            for PreparedBeatmap { beatmap, .. } in prepared.iter().filter(|p| p.beatmap.file_md5 != checksum) {
+                // Un sibling déjà en file n'a plus besoin d'être réclamé
+                FailedQuery::delete_by_hash(pool, &beatmap.file_md5).await?;
+                PendingBeatmap::delete_by_hash(pool, &beatmap.file_md5).await?;
+                events::publish(QueueEvent::Processed {
+                    hash: beatmap.file_md5.clone(),
+                    beatmapset_id: beatmapset.osu_id,
+                });
+                info!("Sibling beatmap processed with success: {}", beatmap.file_md5);
+            }
         }
 
-        let mut beatmapset = BeatmapsetExtended::from(*beatmap_extended.mapset.clone().map_or(Err(anyhow::anyhow!("Beatmapset not found")), |b| Ok(b))?);
-        let mut beatmap = BeatmapExtended::from(beatmap_extended);
+        requested
+    }
 
-        let osu_file = osu_file_from_url(&beatmap.file_path).await.map_err(|e| anyhow::anyhow!("Failed to get osu file: {}", e))?;
-        let notes = osu_to_notes(&osu_file).map_err(|e| anyhow::anyhow!("Failed to convert osu file to notes: {}", e))?;
-        let mut msd: Vec<MSDExtended> = self.calculate_msd(notes).await?;
+    /// Les autres difficultés du set à traiter : autorisées, pas encore en base,
+    /// ni bloquées par un échec, ni en cours chez un autre worker
+    async fn pending_siblings(
+        &self,
+        checksum: &str,
+        maps: Vec<BmExtended>,
+    ) -> Result<Vec<BmExtended>, ProcessError> {
+        let db_ref = self.db.as_ref().ok_or_else(|| ProcessError::Database("Database not initialized".to_string()))?;
+        let pool = db_ref.get_pool();
 
-        self.insert_into_db(&mut beatmapset, &mut beatmap, &mut msd).await?;
-        Ok(())
+        let mut candidates = Vec::new();
+        for map in maps {
+            let Some(hash) = map.checksum.clone() else { continue };
+            if hash != checksum && is_allowed_beatmap(map.mode, map.cs).await {
+                candidates.push(map);
+            }
+        }
+
+        let hashes: Vec<String> = candidates.iter().filter_map(|m| m.checksum.clone()).collect();
+        let processed: HashSet<String> = BeatmapExtended::existing_checksums(pool, &hashes).await?.into_iter().collect();
+        let blocked: HashSet<String> = FailedQuery::blocked_hashes(pool, &hashes).await?.into_iter().collect();
+
+        let mut siblings = Vec::new();
+        for map in candidates {
+            let hash = map.checksum.clone().unwrap_or_default();
+            if processed.contains(&hash) || blocked.contains(&hash) {
+                continue;
+            }
+            if PendingBeatmap::is_claimed_by_hash(pool, &hash).await? {
+                continue;
+            }
+            siblings.push(map);
+        }
+
+        Ok(siblings)
     }
 
-    pub async fn is_already_processed(&self, checksum: String) -> Result<bool> {
-        let db_ref = self.db.as_ref().ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
+    /// Récupère le .osu d'une difficulté (cache disque ou téléchargement), ses statistiques,
+    /// sa timeline de densité et ses MSD
+    async fn prepare_beatmap(
+        &self,
+        beatmap: BmExtended,
+    ) -> Result<PreparedBeatmap, ProcessError> {
+        let mut beatmap = BeatmapExtended::from(beatmap);
+
+        let osu_file = OsuFileStore::instance()
+            .fetch(&beatmap.file_md5, &beatmap.file_path)
+            .await?;
+        let chart = osu_to_notes(&osu_file).map_err(ProcessError::Parse)?;
+        beatmap.set_chart_stats(&chart);
+        let density = chart.timeline(DENSITY_WINDOW_MS);
+
+        // Les autres keymodes sont gardés sans MSD
+        // MinaCalc est synchrone et attend CALC_LOCK : hors des threads du runtime
+        let msd: Vec<MSDExtended> = if chart.has_msd() {
+            tokio::task::spawn_blocking(move || BeatmapProcessor::instance().calculate_msd(chart.notes))
+                .await
+                .map_err(|e| ProcessError::Calc(e.to_string()))?
+                .map_err(|e| ProcessError::Calc(e.to_string()))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(PreparedBeatmap { beatmap, msd, density })
+    }
+
+    pub async fn is_already_processed(&self, checksum: String) -> Result<bool, ProcessError> {
+        let db_ref = self.db.as_ref().ok_or_else(|| ProcessError::Database("Database not initialized".to_string()))?;
 
-        // Vérifier si un échec a été enregistré
-        if FailedQuery::exists_by_hash(db_ref.get_pool(), &checksum).await? {
+        // Échec permanent, ou prochaine tentative pas encore due
+        if FailedQuery::is_blocked_by_hash(db_ref.get_pool(), &checksum).await? {
             return Ok(true);
         }
 
@@ -69,25 +250,35 @@ impl BeatmapProcessor {
         Ok(false)
     }
 
-    /// Insère beatmapset, beatmap et MSD dans la DB
+    /// Insère le beatmapset une seule fois, puis chaque beatmap, toutes ses MSD et sa timeline.
+    ///
+    /// Tout passe dans une transaction : une beatmap n'existe jamais avec une partie de ses rates.
+    /// Retourne l'id en base du beatmapset.
     pub async fn insert_into_db(
         &self,
         beatmapset: &mut BeatmapsetExtended,
-        beatmap: &mut BeatmapExtended,
-        msd: &mut Vec<MSDExtended>,
-    ) -> Result<()> {
+        beatmaps: &mut [PreparedBeatmap],
+    ) -> Result<i32> {
         let db_ref = self.db.as_ref().ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
+        let mut tx = db_ref.get_pool().begin().await?;
 
-        let beatmapset_id = beatmapset.insert_into_db(db_ref.get_pool()).await?;
-        beatmap.beatmapset_id = Some(beatmapset_id);
+        let beatmapset_id = beatmapset.insert_into_db(&mut *tx).await?;
 
-        let beatmap_id = beatmap.insert_into_db(db_ref.get_pool()).await?;
+        for PreparedBeatmap { beatmap, msd, density } in beatmaps.iter_mut() {
+            beatmap.beatmapset_id = Some(beatmapset_id);
+            // Déjà insérée par un autre worker qui traitait une difficulté sœur
+            let Some(beatmap_id) = beatmap.insert_into_db(&mut *tx).await? else {
+                continue;
+            };
 
-        for msd in msd.iter_mut() {
-            msd.beatmap_id = Some(beatmap_id);
-            msd.insert_into_db(db_ref.get_pool()).await?;
+            for msd in msd.iter_mut() {
+                msd.beatmap_id = Some(beatmap_id);
+            }
+            MSDExtended::insert_many_into_db(&mut *tx, msd).await?;
+            BeatmapDensity::upsert(&mut *tx, beatmap_id, density).await?;
         }
 
-        Ok(())
+        tx.commit().await?;
+        Ok(beatmapset_id)
     }
 }
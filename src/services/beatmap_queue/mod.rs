@@ -1,5 +1,8 @@
+pub mod error;
+pub mod events;
 pub mod handler;
 pub mod msd;
 pub mod processor;
 pub mod queue;
 pub mod thread;
+pub mod tracker;
@@ -1,13 +1,17 @@
-use super::processor::BeatmapProcessor;
+use super::processor::{BeatmapProcessor, CALC_LOCK};
 use crate::models::extended::msd::MSDExtended;
 use crate::services::beatmap_queue::processor::BeatmapProcessor as Processor;
-use crate::services::msd_calculator::calculate_etterna_rating;
+use crate::services::msd_calculator::{
+    MSD_MIN_RATE, MSD_RATE_STEP, calculate_etterna_rating, calculate_ssr_at_rate,
+};
 use anyhow::Result;
 use minacalc_rs::Note;
 
 impl BeatmapProcessor {
-    pub async fn calculate_msd(&self, notes: Vec<Note>) -> Result<Vec<MSDExtended>> {
+    /// MSD de la grille 0.7–2.0 ; bloquant (CALC_LOCK), via spawn_blocking
+    pub fn calculate_msd(&self, notes: Vec<Note>) -> Result<Vec<MSDExtended>> {
         if let Some(calc) = Processor::get_calc() {
+            let _guard = CALC_LOCK.lock().unwrap();
             let rating =
                 calculate_etterna_rating(&notes, calc).map_err(|e| anyhow::anyhow!("{}", e))?;
             Ok(rating
@@ -15,7 +19,7 @@ impl BeatmapProcessor {
                 .iter()
                 .enumerate()
                 .map(|(i, msd)| {
-                    let rate = 0.7 + 0.1 * i as f32;
+                    let rate = MSD_MIN_RATE + MSD_RATE_STEP * i as f32;
                     MSDExtended::from(*msd, rate)
                 })
                 .collect::<Vec<_>>())
@@ -23,4 +27,15 @@ impl BeatmapProcessor {
             Err(anyhow::anyhow!("Calc not initialized"))
         }
     }
+
+    /// MSD à un rate hors de la grille 0.7–2.0 ; bloquant (CALC_LOCK), via spawn_blocking
+    pub fn calculate_msd_at_rate(&self, notes: Vec<Note>, rate: f32) -> Result<MSDExtended> {
+        if let Some(calc) = Processor::get_calc() {
+            let _guard = CALC_LOCK.lock().unwrap();
+            let ssr = calculate_ssr_at_rate(&notes, calc, rate).map_err(|e| anyhow::anyhow!("{}", e))?;
+            Ok(MSDExtended::from(ssr, rate))
+        } else {
+            Err(anyhow::anyhow!("Calc not initialized"))
+        }
+    }
 }
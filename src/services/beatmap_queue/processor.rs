@@ -7,6 +7,9 @@ static INIT: Once = Once::new();
 static mut CALC: *mut Calc = ptr::null_mut();
 static PROCESSOR: Mutex<Option<Arc<Mutex<BeatmapProcessor>>>> = Mutex::new(None);
 
+/// MinaCalc n'est pas thread-safe : les workers doivent sérialiser leurs calculs.
+pub static CALC_LOCK: Mutex<()> = Mutex::new(());
+
 #[derive(Clone)]
 pub struct BeatmapProcessor {
     pub db: Option<DatabaseManager>,
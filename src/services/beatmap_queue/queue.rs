@@ -4,17 +4,17 @@ use anyhow::Result;
 use tracing::error;
 
 impl BeatmapProcessor {
-    pub async fn pending_beatmap(&self) -> Result<Option<PendingBeatmap>> {
+    pub async fn claim_pending_beatmap(&self, lease_secs: u64) -> Result<Option<PendingBeatmap>> {
         if let Some(db) = &self.db {
-            match PendingBeatmap::oldest(db.get_pool()).await {
+            match PendingBeatmap::claim_next(db.get_pool(), lease_secs).await {
                 Ok(p) => Ok(p),
                 Err(e) => {
                     error!(
-                        "Error retrieving oldest pending_beatmap: {}",
+                        "Error claiming pending_beatmap: {}",
                         e
                     );
                     Err(anyhow::anyhow!(
-                        "Error retrieving oldest pending_beatmap: {}",
+                        "Error claiming pending_beatmap: {}",
                         e
                     ))
                 }
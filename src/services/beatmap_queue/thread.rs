@@ -1,37 +1,167 @@
+use crate::config::QueueConfig;
+use crate::models::failed_query::FailedQuery;
+use crate::models::pending_batch::PendingBatch;
+use crate::models::pending_beatmap::PendingBeatmap;
+use crate::services::beatmap_queue::handler::handle_pending;
 use crate::services::beatmap_queue::processor::BeatmapProcessor;
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::thread;
+use std::thread::{self, JoinHandle};
 use std::time::Duration;
-use tracing::{error, info};
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tracing::{error, info, warn};
+
+/// Intervalle entre deux remises en file des échecs transitoires
+const RETRY_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+/// Intervalle entre deux purges des batches terminés
+const BATCH_SWEEP_INTERVAL: Duration = Duration::from_secs(3600);
 
 static PROCESSING_THREAD_RUNNING: AtomicBool = AtomicBool::new(false);
+static PROCESSING_THREAD: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+static SHUTDOWN: Lazy<watch::Sender<bool>> = Lazy::new(|| watch::channel(false).0);
 
 impl BeatmapProcessor {
-    pub fn start_processing_thread(&mut self) {
-        if PROCESSING_THREAD_RUNNING.load(Ordering::Relaxed) {
+    pub fn start_processing_thread(&mut self, config: &QueueConfig) {
+        if PROCESSING_THREAD_RUNNING.swap(true, Ordering::Relaxed) {
             error!("Processing thread already running, impossible to restart");
             return;
         }
 
-        PROCESSING_THREAD_RUNNING.store(true, Ordering::Relaxed);
-        self.spawn_processing_thread();
+        self.spawn_processing_thread(config.clone());
+    }
+
+    /// Demande l'arrêt : les workers ne réclament plus de nouvelles lignes, les flux de suivi se ferment
+    pub fn request_shutdown() {
+        SHUTDOWN.send_replace(true);
+    }
+
+    /// Se termine dès que l'arrêt est demandé
+    pub async fn wait_for_shutdown() {
+        let mut rx = SHUTDOWN.subscribe();
+        let _ = rx.wait_for(|stop| *stop).await;
     }
 
-    fn spawn_processing_thread(&self) {    
-        thread::spawn(move || {
+    /// Demande l'arrêt des workers et attend la fin des traitements en cours.
+    ///
+    /// Les workers ne réclament plus de nouvelles lignes, mais chaque beatmap
+    /// déjà réclamée est traitée jusqu'au bout.
+    pub async fn stop_processing_thread() {
+        Self::request_shutdown();
+
+        let handle = PROCESSING_THREAD.lock().unwrap().take();
+        if let Some(handle) = handle {
+            info!("Waiting for in-flight beatmaps to finish");
+            match tokio::task::spawn_blocking(move || handle.join()).await {
+                Ok(Ok(())) => info!("Processing thread stopped"),
+                _ => error!("Processing thread panicked during shutdown"),
+            }
+        }
+    }
+
+    fn spawn_processing_thread(&self, config: QueueConfig) {
+        let handle = thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(async {
-                info!("Processing thread started");
-
-                loop {
-                    let processor = BeatmapProcessor::instance();
-                    if let Ok(Some(pending)) = processor.pending_beatmap().await {
-                        let _ = crate::services::beatmap_queue::handler::handle_pending(&pending).await;
-                    } else {
-                        tokio::time::sleep(Duration::from_secs(10)).await;
+            rt.block_on(async move {
+                let workers = config.workers.max(1);
+                info!("Processing thread started with {} workers", workers);
+
+                let mut pool = JoinSet::new();
+                for worker_id in 0..workers {
+                    pool.spawn(run_worker(worker_id, config.clone(), SHUTDOWN.subscribe()));
+                }
+                pool.spawn(run_retry_sweeper(SHUTDOWN.subscribe()));
+                pool.spawn(run_batch_sweeper(config.batch_retention_days, SHUTDOWN.subscribe()));
+
+                while let Some(result) = pool.join_next().await {
+                    if let Err(e) = result {
+                        error!("Worker crashed: {}", e);
                     }
                 }
             });
         });
+
+        *PROCESSING_THREAD.lock().unwrap() = Some(handle);
+    }
+}
+
+async fn run_worker(worker_id: usize, config: QueueConfig, mut shutdown: watch::Receiver<bool>) {
+    let idle_sleep = Duration::from_secs(config.idle_sleep_secs);
+
+    while !*shutdown.borrow() {
+        let processor = BeatmapProcessor::instance();
+        match processor.claim_pending_beatmap(config.lease_secs).await {
+            Ok(Some(pending)) => handle_with_lease(&pending, &config).await,
+            _ => {
+                // Queue vide (ou DB indisponible) : on attend, sauf si on nous demande de s'arrêter
+                tokio::select! {
+                    _ = tokio::time::sleep(idle_sleep) => {}
+                    _ = shutdown.changed() => {}
+                }
+            }
+        }
+    }
+
+    info!("Worker {} stopped", worker_id);
+}
+
+/// Traite un pending_beatmap en prolongeant son bail : un beatmapset long ne doit pas être repris par un autre worker
+async fn handle_with_lease(pending: &PendingBeatmap, config: &QueueConfig) {
+    let renew_every = Duration::from_secs((config.lease_secs / 3).max(1));
+    let mut renew = tokio::time::interval_at(tokio::time::Instant::now() + renew_every, renew_every);
+    let work = handle_pending(pending, config.max_attempts);
+    tokio::pin!(work);
+
+    loop {
+        tokio::select! {
+            _ = &mut work => break,
+            _ = renew.tick() => {
+                let Some(db) = &BeatmapProcessor::instance().db else { continue };
+                if let Err(e) = PendingBeatmap::renew_lease(db.get_pool(), pending.id, config.lease_secs).await {
+                    warn!("Failed to renew lease of checksum {}: {}", pending.hash, e);
+                }
+            }
+        }
+    }
+}
+
+/// Remet périodiquement en file les échecs transitoires dont le backoff est écoulé
+async fn run_retry_sweeper(mut shutdown: watch::Receiver<bool>) {
+    while !*shutdown.borrow() {
+        if let Some(db) = &BeatmapProcessor::instance().db {
+            match FailedQuery::requeue_due(db.get_pool()).await {
+                Ok(0) => {}
+                Ok(count) => info!("Requeued {} failed checksums for retry", count),
+                Err(e) => error!("Error requeuing failed checksums: {}", e),
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(RETRY_SWEEP_INTERVAL) => {}
+            _ = shutdown.changed() => {}
+        }
+    }
+
+    info!("Retry sweeper stopped");
+}
+
+/// Purge périodiquement les batches terminés une fois leur délai de rétention écoulé
+async fn run_batch_sweeper(retention_days: u32, mut shutdown: watch::Receiver<bool>) {
+    while !*shutdown.borrow() {
+        if let Some(db) = &BeatmapProcessor::instance().db {
+            match PendingBatch::delete_finished_before(db.get_pool(), retention_days).await {
+                Ok(0) => {}
+                Ok(count) => info!("Deleted {} finished batches older than {} days", count, retention_days),
+                Err(e) => error!("Error deleting finished batches: {}", e),
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(BATCH_SWEEP_INTERVAL) => {}
+            _ = shutdown.changed() => {}
+        }
     }
+
+    info!("Batch sweeper stopped");
 }
@@ -0,0 +1,156 @@
+use crate::models::extended::beatmap::BeatmapExtended;
+use crate::models::failed_query::{FailedQuery, FailureReason};
+use crate::models::pending_beatmap::PendingBeatmap;
+use crate::services::beatmap_queue::events::{self, QueueEvent};
+use crate::services::beatmap_queue::processor::BeatmapProcessor;
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{Instant, Interval};
+
+/// Filet de sécurité : l'état est relu à cet intervalle même sans événement
+const RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Les mouvements de la file sont regroupés : la position n'est relue qu'une fois par rafale
+const POSITION_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// État d'un checksum tel que vu par un client qui attend son traitement
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TrackState {
+    Queued { position: i64, total: i64 },
+    Processing,
+    Processed { beatmapset_id: Option<i32> },
+    /// `permanent: false` : échec transitoire, le checksum sera retenté
+    Failed { reason: Option<FailureReason>, permanent: bool },
+}
+
+impl TrackState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrackState::Queued { .. } => "queued",
+            TrackState::Processing => "processing",
+            TrackState::Processed { .. } => "processed",
+            TrackState::Failed { .. } => "failed",
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        matches!(self, TrackState::Processed { .. } | TrackState::Failed { permanent: true, .. })
+    }
+}
+
+/// Calcule l'état actuel d'un checksum, `None` s'il est inconnu
+pub async fn current_state(pool: &PgPool, hash: &str) -> Result<Option<TrackState>, sqlx::Error> {
+    if let Some(beatmapset_id) = BeatmapExtended::beatmapset_osu_id_by_checksum(pool, hash).await? {
+        return Ok(Some(TrackState::Processed { beatmapset_id }));
+    }
+
+    if PendingBeatmap::is_claimed_by_hash(pool, hash).await? {
+        return Ok(Some(TrackState::Processing));
+    }
+
+    if let Some(position) = PendingBeatmap::position_by_hash(pool, hash).await? {
+        let total = PendingBeatmap::count(pool).await?;
+        return Ok(Some(TrackState::Queued { position, total }));
+    }
+
+    if let Some(failed) = FailedQuery::find_by_hash(pool, hash).await? {
+        return Ok(Some(TrackState::Failed {
+            reason: Some(failed.reason),
+            permanent: failed.permanent,
+        }));
+    }
+
+    Ok(None)
+}
+
+struct Tracker {
+    pool: PgPool,
+    hash: String,
+    rx: broadcast::Receiver<QueueEvent>,
+    recheck: Interval,
+    /// Relecture de la position prévue après un mouvement de la file
+    pending_refresh: Option<Instant>,
+    last: Option<TrackState>,
+}
+
+impl Tracker {
+    /// Attend le prochain changement d'état, `None` si le flux doit s'arrêter
+    async fn next_state(&mut self) -> Option<TrackState> {
+        if self.last.is_none() {
+            return self.refresh().await;
+        }
+
+        loop {
+            let deadline = self.pending_refresh;
+            let state = tokio::select! {
+                event = self.rx.recv() => match event {
+                    Ok(QueueEvent::Claimed { hash }) if hash == self.hash => Some(TrackState::Processing),
+                    Ok(QueueEvent::Processed { hash, beatmapset_id }) if hash == self.hash => {
+                        Some(TrackState::Processed { beatmapset_id })
+                    }
+                    Ok(QueueEvent::Failed { hash, reason: Some(reason) })
+                        if hash == self.hash && !reason.is_transient() =>
+                    {
+                        Some(TrackState::Failed { reason: Some(reason), permanent: true })
+                    }
+                    // Transitoire : retenté, sauf si les tentatives sont épuisées, ce que seule la base sait
+                    Ok(QueueEvent::Failed { hash, .. }) if hash == self.hash => self.refresh().await,
+                    // Un événement nous concernant a peut-être été perdu
+                    Err(RecvError::Lagged(_)) => self.refresh().await,
+                    // Un autre checksum a bougé : la position a peut-être changé
+                    Ok(_) => {
+                        if matches!(self.last, Some(TrackState::Queued { .. })) && self.pending_refresh.is_none() {
+                            self.pending_refresh = Some(Instant::now() + POSITION_DEBOUNCE);
+                        }
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+                _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                    self.refresh().await
+                }
+                _ = self.recheck.tick() => self.refresh().await,
+            };
+
+            let state = state?;
+            if self.last.as_ref() != Some(&state) {
+                return Some(state);
+            }
+        }
+    }
+
+    async fn refresh(&mut self) -> Option<TrackState> {
+        self.pending_refresh = None;
+        match current_state(&self.pool, &self.hash).await {
+            // Sorti de la file sans résultat connu (purgé entre-temps)
+            Ok(state) => Some(state.unwrap_or(TrackState::Failed { reason: None, permanent: true })),
+            Err(_) => None,
+        }
+    }
+}
+
+/// Flux des changements d'état d'un checksum, terminé après l'état final ou à l'arrêt du serveur
+pub fn track(pool: PgPool, hash: String) -> impl Stream<Item = TrackState> {
+    let tracker = Tracker {
+        pool,
+        hash,
+        rx: events::subscribe(),
+        recheck: tokio::time::interval_at(Instant::now() + RECHECK_INTERVAL, RECHECK_INTERVAL),
+        pending_refresh: None,
+        last: None,
+    };
+
+    futures::stream::unfold(Some(tracker), |tracker| async move {
+        let mut tracker = tracker?;
+        let state = tracker.next_state().await?;
+        tracker.last = Some(state.clone());
+
+        let next = if state.is_final() { None } else { Some(tracker) };
+        Some((state, next))
+    })
+    // L'arrêt gracieux attend la fermeture de chaque connexion
+    .take_until(BeatmapProcessor::wait_for_shutdown())
+}
@@ -1,4 +1,8 @@
 pub mod beatmap_queue;
 pub mod msd_calculator;
 pub mod osu_api;
+pub mod osu_file_store;
+pub mod osz_import;
+pub mod rate_msd;
+pub mod recalculation;
 pub mod status;
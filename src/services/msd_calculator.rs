@@ -1,29 +1,66 @@
-use minacalc_rs::{Calc, MsdForAllRates, Note};
+use minacalc_rs::{Calc, MsdForAllRates, Note, Ssr};
+use crate::helpers::osu_file::read_circle_size;
+use crate::models::beatmap_density::DensityTimeline;
 use om_fast_parser::{HitObjectType, OsuParser};
 
-/// Converts X position of a note to bitflag for 4K
-fn get_columns(x: i32) -> Result<u32, String> {
-    match x {
-        64 => Ok(1),  // bit flag 0b0001
-        192 => Ok(2), // bit flag 0b0010
-        320 => Ok(4), // bit flag 0b0100
-        448 => Ok(8), // bit flag 0b1000
-        _ => Err(format!("not supported columns {x}")),
+/// Keymodes supportés, de 4K à 10K
+pub const MIN_KEYS: u32 = 4;
+pub const MAX_KEYS: u32 = 10;
+
+/// MinaCalc ne note que le 4K
+pub const MSD_KEYS: u32 = 4;
+
+/// Rates calculés par `calc_msd` : de 0.7 à 2.0 par pas de 0.1
+pub const MSD_MIN_RATE: f32 = 0.7;
+pub const MSD_RATE_STEP: f32 = 0.1;
+
+/// Score visé par les MSD d'Etterna (93 %), en fraction comme l'attend MinaCalc
+const MSD_SCORE_GOAL: f32 = 0.93;
+
+/// Converts X position of a note to a column bitflag: column = floor(x * keys / 512)
+pub fn get_columns(x: i32, keys: u32) -> Result<u32, String> {
+    if !(MIN_KEYS..=MAX_KEYS).contains(&keys) {
+        return Err(format!("not supported key count {keys}"));
     }
+    // Comme osu!, une position hors du playfield tombe sur la colonne la plus proche
+    let column = (x.max(0) as u32 * keys / 512).min(keys - 1);
+    Ok(1 << column)
+}
+
+/// Long note : début et fin en ms
+#[derive(Debug, Clone, Copy)]
+pub struct HoldNote {
+    pub start_ms: i32,
+    pub end_ms: i32,
 }
 
-/// Converts a HitObject to Note for MinaCalc
-fn hit_object_to_note(hit_object: &om_fast_parser::HitObject) -> Result<Note, String> {
+/// Converts a HitObject to Note for MinaCalc, keeping the hold end time aside.
+///
+/// MinaCalc ne lit que les têtes de notes : la fin des LN ne sert qu'aux statistiques LN.
+fn hit_object_to_note(
+    hit_object: &om_fast_parser::HitObject,
+    keys: u32,
+) -> Result<(Note, Option<HoldNote>), String> {
     let time = (hit_object.time as f32) / 1000.0; // Convert ms to seconds
+    let note = Note {
+        notes: get_columns(hit_object.x, keys)?,
+        row_time: time,
+    };
     match hit_object.object_type {
-        HitObjectType::Circle => Ok(Note {
-            notes: get_columns(hit_object.x)?,
-            row_time: time,
-        }),
-        HitObjectType::Hold => Ok(Note {
-            notes: get_columns(hit_object.x)?,
-            row_time: time,
-        }),
+        HitObjectType::Circle => Ok((note, None)),
+        HitObjectType::Hold => {
+            let end_ms = hit_object
+                .end_time
+                .filter(|end| *end >= hit_object.time)
+                .ok_or_else(|| format!("invalid hold end at {}ms", hit_object.time))?;
+            Ok((
+                note,
+                Some(HoldNote {
+                    start_ms: hit_object.time,
+                    end_ms,
+                }),
+            ))
+        }
     }
 }
 
@@ -63,23 +100,148 @@ fn merge_notes_at_same_time(mut raw_notes: Vec<Note>) -> Vec<Note> {
     notes
 }
 
-pub fn osu_to_notes(content: &str) -> Result<Vec<Note>, String> {
+/// Notes d'une map mania, regroupées par ligne, et son nombre de colonnes
+pub struct ManiaChart {
+    pub key_count: u32,
+    pub notes: Vec<Note>,
+    pub holds: Vec<HoldNote>,
+}
+
+/// Densité de notes d'une map, en notes par seconde
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoteDensity {
+    pub avg_nps: f32,
+    /// Maximum sur une fenêtre glissante d'une seconde
+    pub peak_nps: f32,
+}
+
+/// Statistiques des long notes d'une map
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LnStats {
+    pub ln_count: u32,
+    /// Part des LN parmi toutes les notes, entre 0 et 1
+    pub ln_ratio: f32,
+    /// Durée moyenne d'une LN, en ms
+    pub avg_ln_length: f32,
+}
+
+impl ManiaChart {
+    pub fn has_msd(&self) -> bool {
+        self.key_count == MSD_KEYS
+    }
+
+    pub fn ln_stats(&self) -> LnStats {
+        let total: u32 = self.notes.iter().map(|n| n.notes.count_ones()).sum();
+        let ln_count = self.holds.len() as u32;
+        if ln_count == 0 || total == 0 {
+            return LnStats::default();
+        }
+
+        let total_length: i64 = self.holds.iter().map(|h| (h.end_ms - h.start_ms) as i64).sum();
+        LnStats {
+            ln_count,
+            ln_ratio: (ln_count as f32 / total as f32).min(1.0),
+            avg_ln_length: total_length as f32 / ln_count as f32,
+        }
+    }
+
+    /// NPS, notes par ligne et charge de chaque colonne, par fenêtre de `window_ms`
+    pub fn timeline(&self, window_ms: i32) -> DensityTimeline {
+        let to_ms = |note: &Note| (note.row_time * 1000.0).round() as i32;
+        let keys = self.key_count as usize;
+        let (Some(first), Some(last)) = (self.notes.first(), self.notes.last()) else {
+            return DensityTimeline {
+                window_ms,
+                key_count: self.key_count as i32,
+                ..Default::default()
+            };
+        };
+
+        let start_ms = to_ms(first);
+        let windows = ((to_ms(last) - start_ms) / window_ms + 1) as usize;
+        let mut notes = vec![0u32; windows];
+        let mut rows = vec![0u32; windows];
+        let mut column_load = vec![vec![0u16; keys]; windows];
+
+        for note in &self.notes {
+            let index = ((to_ms(note) - start_ms) / window_ms) as usize;
+            notes[index] += note.notes.count_ones();
+            rows[index] += 1;
+            for (column, load) in column_load[index].iter_mut().enumerate() {
+                if note.notes & (1 << column) != 0 {
+                    *load = load.saturating_add(1);
+                }
+            }
+        }
+
+        DensityTimeline {
+            window_ms,
+            start_ms,
+            key_count: self.key_count as i32,
+            nps: notes.iter().map(|n| *n as f32 * 1000.0 / window_ms as f32).collect(),
+            chord_density: notes
+                .iter()
+                .zip(&rows)
+                .map(|(n, r)| if *r > 0 { *n as f32 / *r as f32 } else { 0.0 })
+                .collect(),
+            column_load,
+        }
+    }
+
+    pub fn density(&self) -> NoteDensity {
+        let (Some(first), Some(last)) = (self.notes.first(), self.notes.last()) else {
+            return NoteDensity::default();
+        };
+        let total: u32 = self.notes.iter().map(|n| n.notes.count_ones()).sum();
+        let span = last.row_time - first.row_time;
+
+        let mut peak = 0;
+        let mut in_window = 0;
+        let mut start = 0;
+        for note in &self.notes {
+            in_window += note.notes.count_ones();
+            while note.row_time - self.notes[start].row_time >= 1.0 {
+                in_window -= self.notes[start].notes.count_ones();
+                start += 1;
+            }
+            peak = peak.max(in_window);
+        }
+
+        NoteDensity {
+            avg_nps: total as f32 / span.max(1.0),
+            peak_nps: peak as f32,
+        }
+    }
+}
+
+pub fn osu_to_notes(content: &str) -> Result<ManiaChart, String> {
     // Load and parse the .osu file with our parser
     let mut parser = OsuParser::new();
     parser
         .parse_content(content)
         .map_err(|e| format!("Failed to parse file: {:?}", e))?;
 
-    // Check that it's a 4K Mania map
+    // Check that it's a Mania map
     if parser.mode != 3 {
         return Err("Map is not mania".into());
     }
 
+    // En mania, CircleSize est le nombre de colonnes
+    let circle_size = read_circle_size(content).ok_or("Missing CircleSize")?;
+    let key_count = circle_size.round() as u32;
+    if circle_size.fract() != 0.0 || !(MIN_KEYS..=MAX_KEYS).contains(&key_count) {
+        return Err(format!("Unsupported key count (CircleSize: {})", circle_size));
+    }
+
     // Convert HitObjects to Notes
     let mut raw_notes = Vec::new();
+    let mut holds = Vec::new();
     for hit_object in &parser.hit_objects {
-        match hit_object_to_note(hit_object) {
-            Ok(note) => raw_notes.push(note),
+        match hit_object_to_note(hit_object, key_count) {
+            Ok((note, hold)) => {
+                raw_notes.push(note);
+                holds.extend(hold);
+            }
             Err(e) => return Err(format!("Error converting hit object: {}", e)),
         }
     }
@@ -87,7 +249,19 @@ pub fn osu_to_notes(content: &str) -> Result<Vec<Note>, String> {
     // Merge notes that have the same time
     let notes = merge_notes_at_same_time(raw_notes);
 
-    Ok(notes)
+    Ok(ManiaChart { key_count, notes, holds })
+}
+
+/// Version du calculateur MinaCalc embarqué, enregistrée avec chaque ligne MSD
+pub fn calc_version() -> i32 {
+    Calc::version()
+}
+
+/// SSR à un rate quelconque, hors de la grille de `calc_msd`
+pub fn calculate_ssr_at_rate(notes: &[Note], calc: &Calc, rate: f32) -> Result<Ssr, String> {
+    calc.calc_ssr(notes, rate, MSD_SCORE_GOAL)
+        .map(Ssr::from)
+        .map_err(|e| format!("Failed to calculate SSR: {}", e))
 }
 
 pub fn calculate_etterna_rating(notes: &Vec<Note>, calc: &Calc) -> Result<MsdForAllRates, String> {
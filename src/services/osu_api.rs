@@ -39,4 +39,10 @@ impl OsuApiService {
         let beatmap = self.client.beatmap().map_id(osu_id as u32).await?;
         Ok(beatmap)
     }
+
+    /// Le beatmapset avec toutes ses difficultés (`maps`)
+    pub async fn beatmapset_by_id(&self, mapset_id: u32) -> Result<BeatmapsetExtended> {
+        let beatmapset = self.client.beatmapset(mapset_id).await?;
+        Ok(beatmapset)
+    }
 }
@@ -0,0 +1,129 @@
+use crate::helpers::beatmap::osu_file_from_url;
+use md5::{Digest, Md5};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+static FILE_STORE: Mutex<Option<Arc<OsuFileStore>>> = Mutex::new(None);
+
+#[derive(Debug, thiserror::Error)]
+pub enum OsuFileError {
+    #[error("Failed to download osu file: {0}")]
+    Download(String),
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+}
+
+/// Cache disque des fichiers .osu, adressé par leur md5.
+///
+/// Un fichier n'est écrit qu'après vérification de son md5, donc tout fichier
+/// présent peut être relu sans réseau (recalcul des MSD, nouvelles tentatives).
+pub struct OsuFileStore {
+    root: PathBuf,
+}
+
+impl OsuFileStore {
+    pub fn instance() -> Arc<Self> {
+        let store = FILE_STORE.lock().unwrap();
+        store
+            .as_ref()
+            .expect("OsuFileStore not initialized. Call initialize() first.")
+            .clone()
+    }
+
+    pub fn initialize(root: impl AsRef<Path>) -> std::io::Result<()> {
+        let root = root.as_ref().to_path_buf();
+        std::fs::create_dir_all(&root)?;
+
+        let mut store = FILE_STORE.lock().unwrap();
+        *store = Some(Arc::new(OsuFileStore { root }));
+        Ok(())
+    }
+
+    /// `<root>/ab/cd/abcd….osu` pour ne pas avoir des millions de fichiers dans un seul dossier
+    fn path_for(&self, md5: &str) -> PathBuf {
+        let md5 = md5.to_ascii_lowercase();
+        let (a, b) = (&md5[0..2], &md5[2..4]);
+        self.root.join(a).join(b).join(format!("{}.osu", md5))
+    }
+
+    fn is_valid_md5(md5: &str) -> bool {
+        md5.len() == 32 && md5.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    pub fn md5_hex(content: &[u8]) -> String {
+        format!("{:x}", Md5::digest(content))
+    }
+
+    /// Texte d'un .osu, décodé seulement après vérification du md5 (BOM retiré, octets invalides remplacés)
+    fn decode(bytes: &[u8]) -> String {
+        String::from_utf8_lossy(bytes).trim_start_matches('\u{feff}').to_string()
+    }
+
+    /// Lit un fichier du cache, `None` s'il est absent ou corrompu
+    pub async fn get(&self, md5: &str) -> Option<String> {
+        if !Self::is_valid_md5(md5) {
+            return None;
+        }
+
+        let path = self.path_for(md5);
+        let bytes = tokio::fs::read(&path).await.ok()?;
+        if !Self::md5_hex(&bytes).eq_ignore_ascii_case(md5) {
+            warn!("Corrupted osu file in cache, removing: {}", path.display());
+            let _ = tokio::fs::remove_file(&path).await;
+            return None;
+        }
+
+        Some(Self::decode(&bytes))
+    }
+
+    /// Écrit un fichier dans le cache (fichier temporaire puis rename, jamais de fichier partiel)
+    pub async fn put(&self, md5: &str, content: &[u8]) -> std::io::Result<()> {
+        if !Self::is_valid_md5(md5) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Invalid md5: {}", md5),
+            ));
+        }
+
+        let path = self.path_for(md5);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let tmp = path.with_extension(format!("osu.{}.tmp", uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp, content).await?;
+        if let Err(e) = tokio::fs::rename(&tmp, &path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Retourne le fichier depuis le cache, ou le télécharge, vérifie son md5 et le met en cache
+    pub async fn fetch(&self, md5: &str, url: &str) -> Result<String, OsuFileError> {
+        if let Some(content) = self.get(md5).await {
+            return Ok(content);
+        }
+
+        // Une réponse non-2xx (429, 5xx) est une erreur réseau, retentée plus tard
+        let bytes = osu_file_from_url(url)
+            .await
+            .map_err(|e| OsuFileError::Download(e.to_string()))?;
+
+        let actual = Self::md5_hex(&bytes);
+        if !actual.eq_ignore_ascii_case(md5) {
+            return Err(OsuFileError::ChecksumMismatch {
+                expected: md5.to_string(),
+                actual,
+            });
+        }
+
+        // Un cache en échec ne doit pas faire échouer le traitement
+        if let Err(e) = self.put(md5, &bytes).await {
+            warn!("Failed to store osu file {}: {}", md5, e);
+        }
+
+        Ok(Self::decode(&bytes))
+    }
+}
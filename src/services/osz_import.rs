@@ -0,0 +1,195 @@
+//! Import d'archives .osz : chaque difficulté mania est notée puis liée à son beatmapset osu!,
+//! ou stockée dans un beatmapset local si l'API osu! ne connaît pas son md5.
+
+use crate::helpers::beatmap::is_allowed_beatmap;
+use crate::helpers::osu_file::{MAX_OSU_FILE_BYTES, parse_osu_metadata, validate_osu_file};
+use crate::models::beatmap_density::DENSITY_WINDOW_MS;
+use crate::models::extended::beatmap::BeatmapExtended;
+use crate::models::extended::beatmapset::BeatmapsetExtended;
+use crate::models::failed_query::FailedQuery;
+use crate::services::beatmap_queue::error::ProcessError;
+use crate::services::beatmap_queue::handler::PreparedBeatmap;
+use crate::services::beatmap_queue::processor::BeatmapProcessor;
+use crate::services::msd_calculator::osu_to_notes;
+use crate::services::osu_file_store::OsuFileStore;
+use anyhow::Result;
+use rosu_v2::model::GameMode;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::{Cursor, Read};
+use tracing::{error, info};
+
+/// Taille maximale d'une archive .osz
+pub const MAX_OSZ_BYTES: usize = 100 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OszImportStatus {
+    /// Connue de l'API osu!, insérée dans son beatmapset
+    Linked,
+    /// Inconnue de l'API osu!, insérée dans le beatmapset local de l'archive
+    Local,
+    AlreadyProcessed,
+    /// Pas du mania, ou keymode non supporté
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OszImportItem {
+    pub file_name: String,
+    pub md5: String,
+    pub version: String,
+    pub status: OszImportStatus,
+    pub message: Option<String>,
+    /// Id osu! du beatmapset pour une difficulté liée
+    pub beatmapset_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OszImportReport {
+    pub items: Vec<OszImportItem>,
+    /// Id en base du beatmapset local créé pour les difficultés inconnues
+    pub local_beatmapset_id: Option<i32>,
+}
+
+/// Les .osu d'une archive, avec leur nom dans l'archive
+fn read_osu_entries(bytes: Vec<u8>) -> Result<Vec<(String, String)>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut entries = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let name = file.name().to_string();
+        if !file.is_file() || !name.to_lowercase().ends_with(".osu") {
+            continue;
+        }
+        if file.size() > MAX_OSU_FILE_BYTES as u64 {
+            error!("Skipping oversized entry {} in osz ({} bytes)", name, file.size());
+            continue;
+        }
+
+        // La taille annoncée par l'en-tête n'engage à rien : lecture bornée
+        let mut content = String::new();
+        if let Err(e) = file.by_ref().take(MAX_OSU_FILE_BYTES as u64 + 1).read_to_string(&mut content) {
+            error!("Skipping unreadable entry {} in osz: {}", name, e);
+            continue;
+        }
+        if content.len() > MAX_OSU_FILE_BYTES {
+            error!("Skipping oversized entry {} in osz (more than {} bytes)", name, MAX_OSU_FILE_BYTES);
+            continue;
+        }
+        entries.push((name, content));
+    }
+
+    Ok(entries)
+}
+
+/// Importe une archive .osz et retourne le statut de chaque .osu qu'elle contient
+pub async fn import_osz(bytes: Vec<u8>) -> Result<OszImportReport> {
+    let entries = tokio::task::spawn_blocking(move || read_osu_entries(bytes)).await??;
+
+    let processor = BeatmapProcessor::instance();
+    let db_ref = processor.db.as_ref().ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
+    let pool = db_ref.get_pool();
+    let store = OsuFileStore::instance();
+
+    let mut items = Vec::new();
+    let mut linked_sets: HashSet<i32> = HashSet::new();
+    let mut local_set = None;
+    let mut local_beatmaps = Vec::new();
+
+    for (file_name, content) in entries {
+        let md5 = OsuFileStore::md5_hex(content.as_bytes());
+        let meta = parse_osu_metadata(&content);
+        let mut item = OszImportItem {
+            file_name,
+            md5: md5.clone(),
+            version: meta.version.clone(),
+            status: OszImportStatus::Failed,
+            message: None,
+            beatmapset_id: None,
+        };
+
+        if !is_allowed_beatmap(GameMode::from(meta.mode as u8), meta.circle_size).await {
+            item.status = OszImportStatus::Skipped;
+            item.message = Some(format!("mode {}, cs {}", meta.mode, meta.circle_size));
+            items.push(item);
+            continue;
+        }
+        if let Err(e) = validate_osu_file(&content) {
+            item.message = Some(e.to_string());
+            items.push(item);
+            continue;
+        }
+
+        // Déjà en base : peut-être liée juste avant, en même temps qu'une autre difficulté du set
+        if let Some(set_osu_id) = BeatmapExtended::beatmapset_osu_id_by_checksum(pool, &md5).await? {
+            item.beatmapset_id = set_osu_id;
+            item.status = match set_osu_id {
+                Some(id) if linked_sets.contains(&id) => OszImportStatus::Linked,
+                _ => OszImportStatus::AlreadyProcessed,
+            };
+            items.push(item);
+            continue;
+        }
+
+        // Le worker relira le fichier depuis le cache au lieu de le télécharger
+        store.put(&md5, content.as_bytes()).await?;
+        // Un import explicite passe outre un échec précédent
+        FailedQuery::delete_by_hash(pool, &md5).await?;
+
+        match processor.process_single_checksum(md5.clone()).await {
+            Ok(set_osu_id) => {
+                if let Some(id) = set_osu_id {
+                    linked_sets.insert(id);
+                }
+                item.status = OszImportStatus::Linked;
+                item.beatmapset_id = set_osu_id;
+            }
+            Err(ProcessError::NotFound(_)) => {
+                let chart = match osu_to_notes(&content) {
+                    Ok(chart) => chart,
+                    Err(e) => {
+                        item.message = Some(e);
+                        items.push(item);
+                        continue;
+                    }
+                };
+                let mut beatmap = BeatmapExtended::from_osu_file(&meta, &md5);
+                beatmap.set_chart_stats(&chart);
+                let density = chart.timeline(DENSITY_WINDOW_MS);
+
+                // MinaCalc est synchrone et attend CALC_LOCK : hors des threads du runtime
+                let msd = if chart.has_msd() {
+                    tokio::task::spawn_blocking(move || BeatmapProcessor::instance().calculate_msd(chart.notes))
+                        .await
+                        .unwrap_or_else(|e| Err(anyhow::anyhow!("MSD calculation crashed: {}", e)))
+                } else {
+                    Ok(Vec::new())
+                };
+                match msd {
+                    Ok(msd) => {
+                        local_set.get_or_insert_with(|| BeatmapsetExtended::from_osu_file(&meta));
+                        local_beatmaps.push(PreparedBeatmap { beatmap, msd, density });
+                        item.status = OszImportStatus::Local;
+                    }
+                    Err(e) => item.message = Some(e.to_string()),
+                }
+            }
+            Err(e) => item.message = Some(e.to_string()),
+        }
+        items.push(item);
+    }
+
+    let local_beatmapset_id = match local_set {
+        Some(mut beatmapset) => {
+            let id = processor.insert_into_db(&mut beatmapset, &mut local_beatmaps).await?;
+            info!("Local beatmapset {} created with {} beatmaps", id, local_beatmaps.len());
+            Some(id)
+        }
+        None => None,
+    };
+
+    Ok(OszImportReport { items, local_beatmapset_id })
+}
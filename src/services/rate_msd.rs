@@ -0,0 +1,88 @@
+//! MSD d'une beatmap à un rate quelconque : ligne stockée, interpolation entre deux
+//! rates stockés, ou calcul à la demande depuis le .osu en cache.
+
+use crate::models::extended::beatmap::BeatmapExtended;
+use crate::models::extended::msd::MSDExtended;
+use crate::helpers::common::to_f32;
+use crate::services::beatmap_queue::processor::BeatmapProcessor;
+use crate::services::msd_calculator::osu_to_notes;
+use crate::services::osu_file_store::OsuFileStore;
+use anyhow::Result;
+use serde::Serialize;
+use sqlx::PgPool;
+
+/// Bornes acceptées pour un calcul à la demande
+pub const MIN_RATE: f32 = 0.5;
+pub const MAX_RATE: f32 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MsdSource {
+    /// Rate stocké tel quel
+    Exact,
+    /// Interpolé entre les deux rates stockés qui l'encadrent
+    Interpolated,
+    /// Calculé par MinaCalc depuis le .osu, hors de la grille stockée
+    Computed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RateMsd {
+    pub source: MsdSource,
+    pub msd: MSDExtended,
+}
+
+/// Rate en centièmes, la précision de `msd.rate`
+fn rate_key(rate: f32) -> i32 {
+    (rate * 100.0).round() as i32
+}
+
+/// MSD de la beatmap au rate demandé, `None` si elle n'en a pas (keymode autre que 4K)
+pub async fn msd_at_rate(
+    pool: &PgPool,
+    beatmap: &BeatmapExtended,
+    rate: f32,
+) -> Result<Option<RateMsd>> {
+    let key = rate_key(rate);
+    let rate = key as f32 / 100.0;
+    let stored = MSDExtended::find_all_by_beatmap_id(pool, beatmap.id).await?;
+    let keyed: Vec<(i32, &MSDExtended)> = stored
+        .iter()
+        .filter_map(|m| m.rate.as_ref().map(|r| (rate_key(to_f32(r)), m)))
+        .collect();
+
+    let lower = keyed.iter().filter(|(k, _)| *k <= key).max_by_key(|(k, _)| *k);
+    let upper = keyed.iter().filter(|(k, _)| *k >= key).min_by_key(|(k, _)| *k);
+
+    match (lower, upper) {
+        (Some((lower_key, msd)), _) if *lower_key == key => {
+            return Ok(Some(RateMsd {
+                source: MsdSource::Exact,
+                msd: (*msd).clone(),
+            }));
+        }
+        (Some((_, lower)), Some((_, upper))) => {
+            return Ok(Some(RateMsd {
+                source: MsdSource::Interpolated,
+                msd: MSDExtended::interpolate(lower, upper, rate),
+            }));
+        }
+        _ => {}
+    }
+
+    // Hors de la grille stockée : MinaCalc sur le .osu
+    let osu_file = OsuFileStore::instance()
+        .fetch(&beatmap.file_md5, &beatmap.file_path)
+        .await?;
+    let chart = osu_to_notes(&osu_file).map_err(|e| anyhow::anyhow!(e))?;
+    if !chart.has_msd() {
+        return Ok(None);
+    }
+
+    // MinaCalc est synchrone et attend CALC_LOCK : hors des threads du runtime
+    let mut msd =
+        tokio::task::spawn_blocking(move || BeatmapProcessor::instance().calculate_msd_at_rate(chart.notes, rate))
+            .await??;
+    msd.beatmap_id = Some(beatmap.id);
+    Ok(Some(RateMsd { source: MsdSource::Computed, msd }))
+}
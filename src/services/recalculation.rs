@@ -0,0 +1,130 @@
+//! Recalcul des MSD produites par une ancienne version de MinaCalc.
+//!
+//! Une seule tâche à la fois ; chaque beatmap voit ses lignes remplacées en une transaction.
+
+use crate::models::extended::beatmap::BeatmapExtended;
+use crate::middleware::cache::invalidate_msd_caches;
+use crate::models::extended::msd::MSDExtended;
+use crate::services::beatmap_queue::processor::BeatmapProcessor;
+use crate::services::msd_calculator::{calc_version, osu_to_notes};
+use crate::services::osu_file_store::OsuFileStore;
+use anyhow::Result;
+use chrono::{NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::sync::Mutex;
+use tracing::{error, info};
+
+/// Beatmaps lues par requête
+const RECALC_BATCH_SIZE: i64 = 100;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RecalcProgress {
+    pub running: bool,
+    pub calc_version: i32,
+    /// Beatmaps à recalculer au lancement
+    pub total: i64,
+    pub processed: i64,
+    pub failed: i64,
+    pub last_error: Option<String>,
+    pub started_at: Option<NaiveDateTime>,
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+static PROGRESS: Lazy<Mutex<RecalcProgress>> = Lazy::new(|| Mutex::new(RecalcProgress::default()));
+
+pub fn progress() -> RecalcProgress {
+    PROGRESS.lock().unwrap().clone()
+}
+
+/// Lance le recalcul en tâche de fond, `false` s'il tourne déjà
+pub async fn start(pool: PgPool) -> Result<bool> {
+    let version = calc_version();
+    let total = BeatmapExtended::count_outdated_msd(&pool, version).await?;
+
+    {
+        let mut progress = PROGRESS.lock().unwrap();
+        if progress.running {
+            return Ok(false);
+        }
+        *progress = RecalcProgress {
+            running: true,
+            calc_version: version,
+            total,
+            started_at: Some(Utc::now().naive_utc()),
+            ..Default::default()
+        };
+    }
+
+    info!("MSD recalculation started: {} beatmaps to calc version {}", total, version);
+    tokio::spawn(async move {
+        if let Err(e) = run(&pool, version).await {
+            error!("MSD recalculation stopped: {}", e);
+            PROGRESS.lock().unwrap().last_error = Some(e.to_string());
+        }
+
+        let mut progress = PROGRESS.lock().unwrap();
+        progress.running = false;
+        progress.finished_at = Some(Utc::now().naive_utc());
+        info!(
+            "MSD recalculation finished: {} processed, {} failed",
+            progress.processed, progress.failed
+        );
+    });
+
+    Ok(true)
+}
+
+async fn run(pool: &PgPool, version: i32) -> Result<()> {
+    // Parcours par id croissant : une beatmap en échec n'est pas relue en boucle
+    let mut after_id = 0;
+    loop {
+        let beatmaps = BeatmapExtended::find_outdated_msd(pool, version, after_id, RECALC_BATCH_SIZE).await?;
+        let Some(last) = beatmaps.last() else { break };
+        after_id = last.id;
+
+        let mut replaced = false;
+        for beatmap in beatmaps {
+            let result = recalculate(pool, &beatmap).await;
+            let mut progress = PROGRESS.lock().unwrap();
+            match result {
+                Ok(()) => {
+                    progress.processed += 1;
+                    replaced = true;
+                }
+                Err(e) => {
+                    error!("Failed to recalculate MSD of beatmap {}: {}", beatmap.id, e);
+                    progress.failed += 1;
+                    progress.last_error = Some(format!("beatmap {}: {}", beatmap.id, e));
+                }
+            }
+        }
+
+        // Les réponses en cache servent encore les MSD de l'ancienne version
+        if replaced {
+            invalidate_msd_caches();
+        }
+    }
+    Ok(())
+}
+
+/// Recalcule les MSD d'une beatmap depuis le .osu (cache disque, sinon téléchargement)
+async fn recalculate(pool: &PgPool, beatmap: &BeatmapExtended) -> Result<()> {
+    let osu_file = OsuFileStore::instance()
+        .fetch(&beatmap.file_md5, &beatmap.file_path)
+        .await?;
+    let chart = osu_to_notes(&osu_file).map_err(|e| anyhow::anyhow!(e))?;
+    if !chart.has_msd() {
+        return Err(anyhow::anyhow!("{}K map has no MSD", chart.key_count));
+    }
+
+    // MinaCalc est synchrone et attend CALC_LOCK : hors des threads du runtime
+    let mut msds =
+        tokio::task::spawn_blocking(move || BeatmapProcessor::instance().calculate_msd(chart.notes)).await??;
+    for msd in msds.iter_mut() {
+        msd.beatmap_id = Some(beatmap.id);
+    }
+    MSDExtended::replace_for_beatmap(pool, beatmap.id, &msds).await?;
+    Ok(())
+}